@@ -0,0 +1,24 @@
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use xor_list::LinkedList;
+
+fuzz_target!(|data: &[u8]| {
+    // Both are built from the same bytes, so they must hold the same elements.
+    let list = match LinkedList::<u8>::arbitrary_take_rest(Unstructured::new(data)) {
+        Ok(list) => list,
+        Err(_) => return,
+    };
+    let vec = Vec::<u8>::arbitrary_take_rest(Unstructured::new(data)).unwrap();
+
+    // Walking the links from either end has to visit every node exactly once.
+    assert_eq!(list.len(), vec.len());
+    assert_eq!(list.iter().count(), list.len());
+    assert!(list.iter().eq(&vec));
+    assert!(list.iter().rev().eq(vec.iter().rev()));
+
+    let round_trip: LinkedList<u8> = list.iter().copied().collect();
+    assert_eq!(round_trip, list);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec);
+});
@@ -0,0 +1,141 @@
+//! The running digest behind the `cached_hash` feature.
+//!
+//! The digest of a list `e_0, ..., e_{n-1}` is `sum(h(e_i) * BASE^i)` in wrapping `u64`
+//! arithmetic, where `h` is FNV-1a over the element's `Hash` impl. Because `BASE` is odd it is
+//! invertible, so elements can be added to or removed from either end in O(1).
+
+use core::hash::{Hash, Hasher};
+
+use crate::LinkedList;
+
+const BASE: u64 = 0x0000_0100_0000_01b3;
+const BASE_INV: u64 = inverse(BASE);
+
+/// Multiplicative inverse of an odd number modulo 2^64 by Newton iteration.
+const fn inverse(a: u64) -> u64 {
+    // `a` is its own inverse modulo 2^3, every step doubles the number of correct bits.
+    let mut x = a;
+    let mut i = 0;
+    while i < 5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(a.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+}
+
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(BASE);
+        }
+    }
+}
+
+fn hash_element<E: Hash>(elem: &E) -> u64 {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    elem.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Computes the digest of `iter` from scratch.
+pub(crate) fn digest<'a, E: Hash + 'a>(iter: impl Iterator<Item = &'a E>) -> u64 {
+    HashCache::new(iter).digest
+}
+
+pub(crate) struct HashCache<E> {
+    // Monomorphized `hash_element::<E>`, which lets the unbounded `push`/`pop` methods
+    // update the digest without an `E: Hash` bound.
+    hash_element: fn(&E) -> u64,
+    pub(crate) digest: u64,
+    // `BASE^len`
+    pow: u64,
+}
+
+impl<E> Clone for HashCache<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for HashCache<E> {}
+
+impl<E> HashCache<E> {
+    pub(crate) fn new<'a>(iter: impl Iterator<Item = &'a E>) -> Self
+    where
+        E: Hash + 'a,
+    {
+        let mut cache = HashCache {
+            hash_element: hash_element::<E>,
+            digest: 0,
+            pow: 1,
+        };
+        iter.for_each(|elem| cache.push_back(elem));
+        cache
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.digest = 0;
+        self.pow = 1;
+    }
+
+    pub(crate) fn push_front(&mut self, elem: &E) {
+        let hash = (self.hash_element)(elem);
+        self.digest = hash.wrapping_add(self.digest.wrapping_mul(BASE));
+        self.pow = self.pow.wrapping_mul(BASE);
+    }
+
+    pub(crate) fn push_back(&mut self, elem: &E) {
+        let hash = (self.hash_element)(elem);
+        self.digest = self.digest.wrapping_add(hash.wrapping_mul(self.pow));
+        self.pow = self.pow.wrapping_mul(BASE);
+    }
+
+    pub(crate) fn pop_front(&mut self, elem: &E) {
+        let hash = (self.hash_element)(elem);
+        self.digest = self.digest.wrapping_sub(hash).wrapping_mul(BASE_INV);
+        self.pow = self.pow.wrapping_mul(BASE_INV);
+    }
+
+    pub(crate) fn pop_back(&mut self, elem: &E) {
+        let hash = (self.hash_element)(elem);
+        self.pow = self.pow.wrapping_mul(BASE_INV);
+        self.digest = self.digest.wrapping_sub(hash.wrapping_mul(self.pow));
+    }
+
+    /// Returns the digest and `BASE^len` of `other`, walking it if it has no cache itself.
+    fn parts_of(&self, other: &LinkedList<E>) -> (u64, u64) {
+        match other.hash_cache {
+            Some(cache) => (cache.digest, cache.pow),
+            None => {
+                let mut cache = HashCache {
+                    digest: 0,
+                    pow: 1,
+                    ..*self
+                };
+                other.iter().for_each(|elem| cache.push_back(elem));
+                (cache.digest, cache.pow)
+            }
+        }
+    }
+
+    /// Accounts for `other` being appended to the list.
+    pub(crate) fn append(&mut self, other: &LinkedList<E>) {
+        let (digest, pow) = self.parts_of(other);
+        self.digest = self.digest.wrapping_add(digest.wrapping_mul(self.pow));
+        self.pow = self.pow.wrapping_mul(pow);
+    }
+
+    /// Accounts for `other` being prepended to the list.
+    pub(crate) fn prepend(&mut self, other: &LinkedList<E>) {
+        let (digest, pow) = self.parts_of(other);
+        self.digest = digest.wrapping_add(self.digest.wrapping_mul(pow));
+        self.pow = self.pow.wrapping_mul(pow);
+    }
+}
@@ -0,0 +1,477 @@
+//! Cursors over a `LinkedList`.
+//!
+//! A cursor points at an element, or at the "ghost" position between the back and the front of
+//! the list, and can move in either direction from there. An xor list has to carry the previous
+//! node around to walk at all, so a cursor is just that walk state made into a value.
+
+use alloc::boxed::Box;
+use core::fmt;
+use core::ptr::NonNull;
+
+use crate::{Link, LinkedList, Node};
+
+/// Where a cursor stands in a list.
+struct Position<E> {
+    // The node before `current`, `None` if `current` is the head or the ghost.
+    prev: Link<E>,
+    current: Link<E>,
+    // The index of `current`, `len` at the ghost position.
+    index: usize,
+}
+
+impl<E> Clone for Position<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Position<E> {}
+
+impl<E> Position<E> {
+    fn front(list: &LinkedList<E>) -> Self {
+        Position {
+            prev: None,
+            current: list.head,
+            index: 0,
+        }
+    }
+
+    fn back(list: &LinkedList<E>) -> Self {
+        Position {
+            prev: list
+                .tail
+                .and_then(|tail| unsafe { (*tail.as_ptr()).xor(None) }),
+            current: list.tail,
+            index: list.len.saturating_sub(1),
+        }
+    }
+
+    fn ghost(list: &LinkedList<E>) -> Self {
+        Position {
+            prev: None,
+            current: None,
+            index: list.len,
+        }
+    }
+
+    fn at(list: &LinkedList<E>, index: usize) -> Self {
+        if index == list.len {
+            return Self::ghost(list);
+        }
+        let (prev, node) = list.node_at(index);
+        Position {
+            prev,
+            current: Some(node),
+            index,
+        }
+    }
+
+    /// The node after `current`. Only meaningful if `current` isn't the ghost.
+    fn next(&self) -> Link<E> {
+        self.current
+            .and_then(|node| unsafe { (*node.as_ptr()).xor(self.prev) })
+    }
+
+    /// The node `move_next` would move to.
+    fn peek_next(&self, list: &LinkedList<E>) -> Link<E> {
+        match self.current {
+            None => list.head,
+            Some(_) => self.next(),
+        }
+    }
+
+    /// The node `move_prev` would move to.
+    fn peek_prev(&self, list: &LinkedList<E>) -> Link<E> {
+        match self.current {
+            None => list.tail,
+            Some(_) => self.prev,
+        }
+    }
+
+    fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    fn move_next(&mut self, list: &LinkedList<E>) {
+        match self.current {
+            None => *self = Self::front(list),
+            Some(node) => {
+                let next = self.next();
+                self.prev = next.and(Some(node));
+                self.current = next;
+                self.index += 1;
+            }
+        }
+    }
+
+    fn move_prev(&mut self, list: &LinkedList<E>) {
+        match (self.current, self.prev) {
+            (None, _) => *self = Self::back(list),
+            (Some(_), None) => *self = Self::ghost(list),
+            (Some(node), Some(prev)) => {
+                self.current = Some(prev);
+                self.prev = unsafe { (*prev.as_ptr()).xor(Some(node)) };
+                self.index -= 1;
+            }
+        }
+    }
+
+    /// Steps off `current` so it can be unlinked, to the node after it or to the ghost position.
+    /// Returns the node and the one before it.
+    fn take_current(&mut self) -> Option<(Link<E>, NonNull<Node<E>>)> {
+        let node = self.current?;
+        let prev = self.prev;
+        self.current = self.next();
+        if self.current.is_none() {
+            self.prev = None;
+        }
+        Some((prev, node))
+    }
+
+    /// Walks to `index`, forwards or backwards through the ghost position, whichever takes
+    /// fewer steps. Returns `false` without moving if `index > len`.
+    fn seek_to(&mut self, list: &LinkedList<E>, index: usize) -> bool {
+        if index > list.len {
+            return false;
+        }
+        // The ghost position makes the list a ring of `len + 1` positions.
+        let positions = list.len + 1;
+        let forward = (index + positions - self.index) % positions;
+        if forward <= positions - forward {
+            for _ in 0..forward {
+                self.move_next(list);
+            }
+        } else {
+            for _ in forward..positions {
+                self.move_prev(list);
+            }
+        }
+        true
+    }
+}
+
+/// A read-only cursor over a `LinkedList`, see `LinkedList::cursor_front`.
+pub struct Cursor<'a, E: 'a> {
+    list: &'a LinkedList<E>,
+    pos: Position<E>,
+}
+
+impl<E> Clone for Cursor<'_, E> {
+    fn clone(&self) -> Self {
+        Cursor {
+            list: self.list,
+            pos: self.pos,
+        }
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for Cursor<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<'a, E> Cursor<'a, E> {
+    pub(crate) fn front(list: &'a LinkedList<E>) -> Self {
+        Cursor {
+            list,
+            pos: Position::front(list),
+        }
+    }
+
+    pub(crate) fn back(list: &'a LinkedList<E>) -> Self {
+        Cursor {
+            list,
+            pos: Position::back(list),
+        }
+    }
+
+    pub(crate) fn at(list: &'a LinkedList<E>, index: usize) -> Self {
+        Cursor {
+            list,
+            pos: Position::at(list, index),
+        }
+    }
+
+    /// Moves the cursor to the next element. From the back this moves to the ghost position,
+    /// and from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        self.pos.move_next(self.list);
+    }
+
+    /// Moves the cursor to the previous element. From the front this moves to the ghost
+    /// position, and from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(self.list);
+    }
+
+    /// Moves the cursor to `index`, or to the ghost position if `index == len`, walking from the
+    /// current position in whichever direction is shorter. Returns `false` without moving if
+    /// `index > len`.
+    pub fn seek_to(&mut self, index: usize) -> bool {
+        self.pos.seek_to(self.list, index)
+    }
+
+    /// Returns the element the cursor points at, or `None` at the ghost position.
+    pub fn current(&self) -> Option<&'a E> {
+        self.pos
+            .current
+            .map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Returns the index of the current element, or `None` at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.pos.index()
+    }
+
+    /// Returns the element after the current one without moving the cursor. At the ghost
+    /// position this is the front element.
+    pub fn peek_next(&self) -> Option<&'a E> {
+        self.pos
+            .peek_next(self.list)
+            .map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Returns the element before the current one without moving the cursor. At the ghost
+    /// position this is the back element.
+    pub fn peek_prev(&self) -> Option<&'a E> {
+        self.pos
+            .peek_prev(self.list)
+            .map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+}
+
+/// A cursor over a `LinkedList` that can also edit it, see `LinkedList::cursor_front_mut`.
+pub struct CursorMut<'a, E: 'a> {
+    list: &'a mut LinkedList<E>,
+    pos: Position<E>,
+}
+
+impl<E: fmt::Debug> fmt::Debug for CursorMut<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<'a, E> CursorMut<'a, E> {
+    pub(crate) fn front(list: &'a mut LinkedList<E>) -> Self {
+        let pos = Position::front(list);
+        CursorMut { list, pos }
+    }
+
+    pub(crate) fn back(list: &'a mut LinkedList<E>) -> Self {
+        let pos = Position::back(list);
+        CursorMut { list, pos }
+    }
+
+    pub(crate) fn at(list: &'a mut LinkedList<E>, index: usize) -> Self {
+        let pos = Position::at(list, index);
+        CursorMut { list, pos }
+    }
+
+    /// Moves the cursor to the next element. From the back this moves to the ghost position,
+    /// and from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        self.pos.move_next(self.list);
+    }
+
+    /// Moves the cursor to the previous element. From the front this moves to the ghost
+    /// position, and from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(self.list);
+    }
+
+    /// Moves the cursor to `index`, or to the ghost position if `index == len`, walking from the
+    /// current position in whichever direction is shorter. Returns `false` without moving if
+    /// `index > len`.
+    pub fn seek_to(&mut self, index: usize) -> bool {
+        self.pos.seek_to(self.list, index)
+    }
+
+    /// Returns the element the cursor points at, or `None` at the ghost position.
+    pub fn current(&mut self) -> Option<&mut E> {
+        self.list.invalidate_hash();
+        self.pos
+            .current
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Returns the index of the current element, or `None` at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.pos.index()
+    }
+
+    /// Returns the element after the current one without moving the cursor. At the ghost
+    /// position this is the front element.
+    pub fn peek_next(&mut self) -> Option<&mut E> {
+        self.list.invalidate_hash();
+        self.pos
+            .peek_next(self.list)
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Returns the element before the current one without moving the cursor. At the ghost
+    /// position this is the back element.
+    pub fn peek_prev(&mut self) -> Option<&mut E> {
+        self.list.invalidate_hash();
+        self.pos
+            .peek_prev(self.list)
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Returns a read-only cursor at the same position, which borrows this one.
+    pub fn as_cursor(&self) -> Cursor<'_, E> {
+        Cursor {
+            list: self.list,
+            pos: self.pos,
+        }
+    }
+
+    /// Inserts `elem` before the current element. At the ghost position it becomes the new back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is at its capacity limit.
+    pub fn insert_before(&mut self, elem: E) {
+        let node = Box::new(Node::new(elem));
+        match self.pos.current {
+            None => self.list.push_back_node(node),
+            Some(_) => {
+                let node = unsafe { self.list.link_node(self.pos.prev, self.pos.current, node) };
+                self.pos.prev = Some(node);
+            }
+        }
+        self.pos.index += 1;
+    }
+
+    /// Inserts `elem` after the current element. At the ghost position it becomes the new front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is at its capacity limit.
+    pub fn insert_after(&mut self, elem: E) {
+        let node = Box::new(Node::new(elem));
+        match self.pos.current {
+            None => {
+                self.list.push_front_node(node);
+                self.pos.index += 1;
+            }
+            Some(_) => unsafe {
+                self.list.link_node(self.pos.current, self.pos.next(), node);
+            },
+        }
+    }
+
+    /// Removes the current element and returns it, moving the cursor to the next element. Does
+    /// nothing at the ghost position.
+    pub fn remove_current(&mut self) -> Option<E> {
+        let (prev, node) = self.pos.take_current()?;
+        unsafe { Some(self.list.unlink_node(prev, node).into_element()) }
+    }
+
+    /// Moves all elements of `list` in between the current element and the one before it, in
+    /// O(1). At the ghost position they are appended at the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements would exceed the list's capacity limit.
+    pub fn splice_before(&mut self, mut list: LinkedList<E>) {
+        let (last, len) = (list.tail, list.len);
+        match self.pos.current {
+            None => self.list.append(&mut list),
+            Some(_) => {
+                unsafe {
+                    self.list
+                        .splice_nodes(self.pos.prev, self.pos.current, &mut list)
+                };
+                self.pos.prev = last.or(self.pos.prev);
+            }
+        }
+        self.pos.index += len;
+    }
+
+    /// Moves all elements of `list` in between the current element and the one after it, in
+    /// O(1). At the ghost position they are prepended at the front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements would exceed the list's capacity limit.
+    pub fn splice_after(&mut self, mut list: LinkedList<E>) {
+        match self.pos.current {
+            None => {
+                self.pos.index += list.len;
+                self.list.append_front(&mut list);
+            }
+            Some(_) => unsafe {
+                self.list
+                    .splice_nodes(self.pos.current, self.pos.next(), &mut list);
+            },
+        }
+    }
+
+    /// Splits the list after the current element and returns everything after it, in O(1). At
+    /// the ghost position the whole list is returned.
+    pub fn split_after(&mut self) -> LinkedList<E> {
+        match (self.pos.current, self.pos.next()) {
+            (None, _) => {
+                self.pos.index = 0;
+                self.list.take_all()
+            }
+            (Some(_), None) => LinkedList::new(),
+            (Some(current), Some(next)) => {
+                let mut front =
+                    unsafe { self.list.split_between(current, next, self.pos.index + 1) };
+                self.list.swap_contents(&mut front);
+                front
+            }
+        }
+    }
+
+    /// Splits the list before the current element and returns everything before it, in O(1).
+    /// At the ghost position the whole list is returned.
+    pub fn split_before(&mut self) -> LinkedList<E> {
+        match (self.pos.current, self.pos.prev) {
+            (None, _) => {
+                self.pos.index = 0;
+                self.list.take_all()
+            }
+            (Some(_), None) => LinkedList::new(),
+            (Some(current), Some(prev)) => {
+                let front = unsafe { self.list.split_between(prev, current, self.pos.index) };
+                self.pos.prev = None;
+                self.pos.index = 0;
+                front
+            }
+        }
+    }
+
+    /// Removes the current element and returns it as a single element list, moving the cursor
+    /// to the next element. The node is handed over as is, so it can be spliced into another list
+    /// without allocating again. Does nothing at the ghost position.
+    pub fn remove_current_as_list(&mut self) -> Option<LinkedList<E>> {
+        let (prev, node) = self.pos.take_current()?;
+        let mut list = LinkedList::new();
+        list.push_back_node(unsafe { self.list.unlink_node(prev, node) });
+        Some(list)
+    }
+}
+
+#[cfg(test)]
+impl<E> CursorMut<'_, E> {
+    /// The node before the current one, which has to be `None` at the ghost position.
+    pub(crate) fn prev_link(&self) -> Link<E> {
+        self.pos.prev
+    }
+}
+
+unsafe impl<E: Sync> Send for Cursor<'_, E> {}
+unsafe impl<E: Sync> Sync for Cursor<'_, E> {}
+
+unsafe impl<E: Send> Send for CursorMut<'_, E> {}
+unsafe impl<E: Sync> Sync for CursorMut<'_, E> {}
@@ -0,0 +1,193 @@
+//! An xor list over nodes owned by the caller.
+//!
+//! `IntrusiveList` links `Node`s that live wherever the caller put them, e.g. in an array or on
+//! the stack, and never allocates. The list mutably borrows every node it holds, so nodes can't be
+//! moved or touched elsewhere until they are popped or unlinked again.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::{Iter, Node};
+
+#[cfg(test)]
+mod tests;
+
+pub struct IntrusiveList<'a, E> {
+    head: Option<NonNull<Node<E>>>,
+    tail: Option<NonNull<Node<E>>>,
+    len: usize,
+    marker: PhantomData<Pin<&'a mut Node<E>>>,
+}
+
+/// Identifies a node linked into an `IntrusiveList`, see `IntrusiveList::unlink`.
+pub struct NodeHandle<E>(NonNull<Node<E>>);
+
+impl<E> Clone for NodeHandle<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for NodeHandle<E> {}
+
+impl<E> PartialEq for NodeHandle<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<E> Eq for NodeHandle<E> {}
+
+impl<E> fmt::Debug for NodeHandle<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NodeHandle").field(&self.0).finish()
+    }
+}
+
+impl<'a, E> IntrusiveList<'a, E> {
+    pub const fn new() -> Self {
+        IntrusiveList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Links `node` in at the front of the list and returns a handle to it.
+    pub fn push_front_node(&mut self, node: Pin<&'a mut Node<E>>) -> NodeHandle<E> {
+        // Only the link is written to, the node is never moved.
+        let node = unsafe { Pin::get_unchecked_mut(node) };
+        node.prev_x_next = 0;
+        node.xor_assign(self.head);
+        let node = NonNull::from(node);
+        match self.head {
+            None => self.tail = Some(node),
+            Some(head) => unsafe { (*head.as_ptr()).xor_assign(Some(node)) },
+        }
+        self.head = Some(node);
+        self.len += 1;
+        NodeHandle(node)
+    }
+
+    /// Links `node` in at the back of the list and returns a handle to it.
+    pub fn push_back_node(&mut self, node: Pin<&'a mut Node<E>>) -> NodeHandle<E> {
+        // Only the link is written to, the node is never moved.
+        let node = unsafe { Pin::get_unchecked_mut(node) };
+        node.prev_x_next = 0;
+        node.xor_assign(self.tail);
+        let node = NonNull::from(node);
+        match self.tail {
+            None => self.head = Some(node),
+            Some(tail) => unsafe { (*tail.as_ptr()).xor_assign(Some(node)) },
+        }
+        self.tail = Some(node);
+        self.len += 1;
+        NodeHandle(node)
+    }
+
+    pub fn pop_front_node(&mut self) -> Option<Pin<&'a mut Node<E>>> {
+        self.head
+            .map(|node| unsafe { self.unlink_node(None, node) })
+    }
+
+    pub fn pop_back_node(&mut self) -> Option<Pin<&'a mut Node<E>>> {
+        self.tail.map(|node| unsafe {
+            let prev = (*node.as_ptr()).xor(None);
+            self.unlink_node(prev, node)
+        })
+    }
+
+    /// Unlinks the node identified by `handle` and hands it back, or returns `None` if it isn't
+    /// linked into this list.
+    ///
+    /// Nodes only store the xor of their neighbours, so finding the node's neighbours takes a
+    /// walk from the front, i.e. this is O(n).
+    pub fn unlink(&mut self, handle: NodeHandle<E>) -> Option<Pin<&'a mut Node<E>>> {
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            if node == handle.0 {
+                return Some(unsafe { self.unlink_node(prev, node) });
+            }
+            cur = unsafe { (*node.as_ptr()).xor(prev) };
+            prev = Some(node);
+        }
+        None
+    }
+
+    /// # Safety
+    ///
+    /// `node` must be linked into this list and `prev` must be the node before it.
+    unsafe fn unlink_node(
+        &mut self,
+        prev: Option<NonNull<Node<E>>>,
+        node: NonNull<Node<E>>,
+    ) -> Pin<&'a mut Node<E>> {
+        let next = (*node.as_ptr()).xor(prev);
+        match prev {
+            None => self.head = next,
+            Some(prev) => {
+                (*prev.as_ptr()).xor_assign(Some(node));
+                (*prev.as_ptr()).xor_assign(next);
+            }
+        }
+        match next {
+            None => self.tail = prev,
+            Some(next) => {
+                (*next.as_ptr()).xor_assign(Some(node));
+                (*next.as_ptr()).xor_assign(prev);
+            }
+        }
+        self.len -= 1;
+        let node = &mut *node.as_ptr();
+        node.prev_x_next = 0;
+        // The node was pinned when it was linked in and hasn't moved since.
+        Pin::new_unchecked(node)
+    }
+
+    pub fn iter(&self) -> Iter<'_, E> {
+        Iter {
+            head: self.head,
+            prev_head: None,
+            tail: self.tail,
+            prev_tail: None,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for IntrusiveList<'_, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for IntrusiveList<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a IntrusiveList<'_, E> {
+    type Item = &'a E;
+    type IntoIter = Iter<'a, E>;
+
+    fn into_iter(self) -> Iter<'a, E> {
+        self.iter()
+    }
+}
+
+unsafe impl<E: Send> Send for IntrusiveList<'_, E> {}
+unsafe impl<E: Sync> Sync for IntrusiveList<'_, E> {}
@@ -0,0 +1,60 @@
+// These tests must also run without the `alloc` feature, so they don't allocate.
+
+use super::*;
+
+use core::ptr;
+
+#[test]
+fn test_intrusive_list() {
+    let mut nodes = [Node::new(1), Node::new(2), Node::new(3), Node::new(4)];
+    let mut list = IntrusiveList::new();
+    let [a, b, c, d] = &mut nodes;
+    let handles = [
+        list.push_back_node(Pin::new(a)),
+        list.push_back_node(Pin::new(b)),
+        list.push_back_node(Pin::new(c)),
+        list.push_back_node(Pin::new(d)),
+    ];
+    assert_eq!(list.len(), 4);
+    assert!(list.iter().eq(&[1, 2, 3, 4]));
+    assert!(list.iter().rev().eq(&[4, 3, 2, 1]));
+
+    let mut third = list.unlink(handles[2]).unwrap();
+    *third.element_mut() = 30;
+    assert!(list.unlink(handles[2]).is_none());
+    assert!(list.iter().eq(&[1, 2, 4]));
+    assert!(list.iter().rev().eq(&[4, 2, 1]));
+
+    list.push_front_node(third);
+    assert!(list.iter().eq(&[30, 1, 2, 4]));
+    assert_eq!(list.pop_back_node().unwrap().element(), &4);
+    assert_eq!(list.pop_front_node().unwrap().element(), &30);
+    assert!(list.unlink(handles[0]).is_some());
+    assert!(list.unlink(handles[1]).is_some());
+    assert!(list.is_empty());
+    assert!(list.pop_front_node().is_none());
+
+    // The nodes are free again once the list is no longer used.
+    assert_eq!(nodes[2].element(), &30);
+}
+
+#[test]
+fn test_intrusive_list_static() {
+    static mut NODES: [Node<u32>; 3] = [Node::new(1), Node::new(2), Node::new(3)];
+
+    // This is the only place that touches `NODES`.
+    let nodes = unsafe { &mut *ptr::addr_of_mut!(NODES) };
+    let mut list = IntrusiveList::new();
+    for node in nodes.iter_mut() {
+        list.push_front_node(Pin::static_mut(node));
+    }
+    assert!(list.iter().eq(&[3, 2, 1]));
+    assert!(list.iter().rev().eq(&[1, 2, 3]));
+
+    let mut middle = list.pop_front_node().unwrap();
+    *middle.element_mut() = 30;
+    list.push_back_node(middle);
+    assert!(list.iter().eq(&[2, 1, 30]));
+    assert!(list.iter().rev().eq(&[30, 1, 2]));
+    assert_eq!(list.len(), 3);
+}
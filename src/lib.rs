@@ -1,30 +1,170 @@
 //! This crate implements an xor doubly-linked list i.e. the `previous` and `next` pointers are
 //! xored together in the lists nodes.
 //! Otherwise this implementation is mostly analogous to `alloc::collections::LinkedList`
+//!
+//! # Features
+//!
+//! `alloc` is on by default and provides `LinkedList` and everything built on it. Without it the
+//! crate doesn't link `alloc` at all and only `IntrusiveList` is available, so it can be used
+//! without a global allocator.
+//!
+//! `cached_hash` changes how every `LinkedList` in the program hashes, because Cargo unifies
+//! features across the dependency graph. Elements are no longer fed to the caller's `Hasher`,
+//! only a digest built with a fixed, unkeyed hash is. Equal lists still hash equally, but a keyed
+//! hasher like `RandomState` no longer protects against crafted collisions, so don't enable it
+//! for maps keyed by untrusted lists.
 #![cfg_attr(not(test), no_std)]
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
 use core::cmp::Ordering;
-use core::fmt;
+#[cfg(feature = "alloc")]
+use core::ffi::c_void;
+#[cfg(feature = "alloc")]
 use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
 use core::iter::FromIterator;
-use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
 use core::mem;
-use core::ptr::NonNull;
+#[cfg(feature = "alloc")]
+use core::ops::{Bound, Index, IndexMut, RangeBounds};
+#[cfg(feature = "alloc")]
+use core::ptr;
 
-#[cfg(test)]
+#[cfg(feature = "cached_hash")]
+mod cached_hash;
+#[cfg(feature = "alloc")]
+mod cursor;
+mod intrusive;
+#[cfg(feature = "alloc")]
+mod sorted;
+#[cfg(all(test, feature = "alloc"))]
 mod tests;
 
+#[cfg(feature = "alloc")]
+pub use cursor::{Cursor, CursorMut};
+pub use intrusive::{IntrusiveList, NodeHandle};
+#[cfg(feature = "alloc")]
+pub use sorted::SortedList;
+
+/// Creates a `LinkedList` containing the arguments, like `vec!`.
+///
+/// `xor_list![a, b, c]` lists the elements, `xor_list![x; n]` repeats a clone of `x` `n` times.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! xor_list {
+    () => {
+        $crate::LinkedList::new()
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::LinkedList::from_elem($elem, $n)
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::LinkedList::from([$($x),+])
+    };
+}
+
+#[cfg(feature = "alloc")]
 pub struct LinkedList<E> {
     head: Option<NonNull<Node<E>>>,
     tail: Option<NonNull<Node<E>>>,
     len: usize,
+    limit: Option<usize>,
+    #[cfg(feature = "cached_hash")]
+    hash_cache: Option<cached_hash::HashCache<E>>,
     phantom: PhantomData<Box<Node<E>>>,
 }
 
+#[cfg(feature = "alloc")]
 impl<E> LinkedList<E> {
+    fn is_full(&self) -> bool {
+        self.limit.is_some_and(|cap| self.len >= cap)
+    }
+
+    fn check_limit(&self, additional: usize) {
+        if let Some(cap) = self.limit {
+            assert!(
+                additional <= cap.saturating_sub(self.len),
+                "capacity limit of {} exceeded",
+                cap
+            );
+        }
+    }
+
+    fn check_cursor_index(&self, index: usize) {
+        assert!(
+            index <= self.len,
+            "Cannot place a cursor at index {} in a list of length {}",
+            index,
+            self.len
+        );
+    }
+
+    /// Resolves `range` to a `start..end` pair within the list.
+    fn range_indices<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range start overflowed"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range end overflowed"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end,
+            "range starts at {} but ends at {}",
+            start,
+            end
+        );
+        assert!(
+            end <= self.len,
+            "range end {} out of bounds for a list of length {}",
+            end,
+            self.len
+        );
+        (start, end)
+    }
+
+    /// Returns the first and last node of `start..end` along with the nodes just outside them,
+    /// in the order the fields of `Iter` expect.
+    fn range_links(&self, start: usize, end: usize) -> [Link<E>; 4] {
+        if start == end {
+            return [None; 4];
+        }
+        let (prev, first) = self.node_at(start);
+        let (before_last, last) = self.node_at(end - 1);
+        let next = unsafe { (*last.as_ptr()).xor(before_last) };
+        [Some(first), prev, Some(last), next]
+    }
+
+    /// Drops the cached hash. Every mutation that can't keep it up to date must call this.
+    fn invalidate_hash(&mut self) {
+        #[cfg(feature = "cached_hash")]
+        {
+            self.hash_cache = None;
+        }
+    }
+
     fn push_front_node(&mut self, mut node: Box<Node<E>>) {
+        self.check_limit(1);
+        #[cfg(feature = "cached_hash")]
+        if let Some(cache) = &mut self.hash_cache {
+            cache.push_front(&node.element);
+        }
         unsafe {
             node.xor_assign(self.head);
             let node = Some(Box::leak(node).into());
@@ -39,19 +179,29 @@ impl<E> LinkedList<E> {
 
     fn pop_front_node(&mut self) -> Option<Box<Node<E>>> {
         self.head.map(|node_ptr| unsafe {
-            let node = Box::from_raw(node_ptr.as_ptr());
+            let mut node = Box::from_raw(node_ptr.as_ptr());
             self.head = node.xor(None);
+            node.prev_x_next = 0;
 
             match self.head {
                 None => self.tail = None,
                 Some(head) => (*head.as_ptr()).xor_assign(Some(node_ptr)),
             }
             self.len -= 1;
+            #[cfg(feature = "cached_hash")]
+            if let Some(cache) = &mut self.hash_cache {
+                cache.pop_front(&node.element);
+            }
             node
         })
     }
 
     fn push_back_node(&mut self, mut node: Box<Node<E>>) {
+        self.check_limit(1);
+        #[cfg(feature = "cached_hash")]
+        if let Some(cache) = &mut self.hash_cache {
+            cache.push_back(&node.element);
+        }
         unsafe {
             node.xor_assign(self.tail);
             let node = Some(Box::leak(node).into());
@@ -64,149 +214,342 @@ impl<E> LinkedList<E> {
         }
     }
 
-    fn pop_back_node(&mut self) -> Option<Box<Node<E>>> {
-        self.tail.map(|node_ptr| unsafe {
-            let node = Box::from_raw(node_ptr.as_ptr());
-            self.tail = node.xor(None);
+    /// Returns the node at `index` and the node before it, walking from whichever end is closer.
+    /// `index` must be less than `len`.
+    fn node_at(&self, index: usize) -> (Link<E>, NonNull<Node<E>>) {
+        debug_assert!(index < self.len);
+        unsafe {
+            if index < self.len / 2 {
+                let mut prev = None;
+                let mut node = self.head.unwrap();
+                for _ in 0..index {
+                    let next = (*node.as_ptr()).xor(prev).unwrap();
+                    prev = Some(node);
+                    node = next;
+                }
+                (prev, node)
+            } else {
+                let mut next = None;
+                let mut node = self.tail.unwrap();
+                for _ in index + 1..self.len {
+                    let prev = (*node.as_ptr()).xor(next).unwrap();
+                    next = Some(node);
+                    node = prev;
+                }
+                ((*node.as_ptr()).xor(next), node)
+            }
+        }
+    }
 
-            match self.tail {
-                None => self.head = None,
-                Some(tail) => (*tail.as_ptr()).xor_assign(Some(node_ptr)),
+    /// Returns the first node whose element matches `pred`, and the node before it.
+    fn find_node<F: FnMut(&E) -> bool>(&self, mut pred: F) -> Option<(Link<E>, NonNull<Node<E>>)> {
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                if pred(&(*node.as_ptr()).element) {
+                    return Some((prev, node));
+                }
+                cur = (*node.as_ptr()).xor(prev);
             }
-            self.len -= 1;
-            node
-        })
+            prev = Some(node);
+        }
+        None
     }
-}
 
-impl<E> LinkedList<E> {
-    pub fn new() -> Self {
-        LinkedList {
-            head: None,
-            tail: None,
-            len: 0,
-            phantom: PhantomData,
+    /// Unlinks `node`, whose predecessor is `prev`, and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list and `prev` the node before it (`None` for the head).
+    unsafe fn unlink_node(
+        &mut self,
+        prev: Option<NonNull<Node<E>>>,
+        node: NonNull<Node<E>>,
+    ) -> Box<Node<E>> {
+        let next = (*node.as_ptr()).xor(prev);
+        match (prev, next) {
+            (None, _) => self.pop_front_node().unwrap(),
+            (_, None) => self.pop_back_node().unwrap(),
+            (Some(prev), Some(next)) => {
+                self.invalidate_hash();
+                (*prev.as_ptr()).xor_assign(Some(node));
+                (*prev.as_ptr()).xor_assign(Some(next));
+                (*next.as_ptr()).xor_assign(Some(node));
+                (*next.as_ptr()).xor_assign(Some(prev));
+                self.len -= 1;
+                let mut node = Box::from_raw(node.as_ptr());
+                node.prev_x_next = 0;
+                node
+            }
         }
     }
 
-    pub fn len(&self) -> usize {
-        self.len
+    /// Links `node` in between the adjacent nodes `prev` and `next` and returns a pointer to it.
+    ///
+    /// # Safety
+    ///
+    /// `prev` and `next` must be adjacent nodes of this list, `None` standing for the ends.
+    unsafe fn link_node(
+        &mut self,
+        prev: Link<E>,
+        next: Link<E>,
+        mut node: Box<Node<E>>,
+    ) -> NonNull<Node<E>> {
+        match (prev, next) {
+            (None, _) => {
+                self.push_front_node(node);
+                self.head.unwrap()
+            }
+            (_, None) => {
+                self.push_back_node(node);
+                self.tail.unwrap()
+            }
+            (Some(prev_ptr), Some(next_ptr)) => {
+                self.check_limit(1);
+                self.invalidate_hash();
+                node.xor_assign(prev);
+                node.xor_assign(next);
+                let node = NonNull::from(Box::leak(node));
+                (*prev_ptr.as_ptr()).xor_assign(next);
+                (*prev_ptr.as_ptr()).xor_assign(Some(node));
+                (*next_ptr.as_ptr()).xor_assign(prev);
+                (*next_ptr.as_ptr()).xor_assign(Some(node));
+                self.len += 1;
+                node
+            }
+        }
     }
 
-    pub fn push_front(&mut self, elem: E) {
-        self.push_front_node(Box::new(Node::new(elem)));
+    /// Links all nodes of `other` in between the adjacent nodes `prev` and `next`, leaving `other`
+    /// empty.
+    ///
+    /// # Safety
+    ///
+    /// `prev` and `next` must be adjacent nodes of this list, `None` standing for the ends.
+    unsafe fn splice_nodes(&mut self, prev: Link<E>, next: Link<E>, other: &mut Self) {
+        match (prev, next, other.head, other.tail) {
+            (None, ..) => self.append_front(other),
+            (_, None, ..) => self.append(other),
+            (Some(prev_ptr), Some(next_ptr), Some(first), Some(last)) => {
+                self.check_limit(other.len);
+                self.invalidate_hash();
+                // The nodes now belong to `self`.
+                let other = mem::ManuallyDrop::new(other.take_all());
+                (*prev_ptr.as_ptr()).xor_assign(next);
+                (*prev_ptr.as_ptr()).xor_assign(Some(first));
+                (*first.as_ptr()).xor_assign(prev);
+                (*last.as_ptr()).xor_assign(next);
+                (*next_ptr.as_ptr()).xor_assign(prev);
+                (*next_ptr.as_ptr()).xor_assign(Some(last));
+                self.len += other.len;
+            }
+            _ => {}
+        }
     }
 
-    pub fn pop_front(&mut self) -> Option<E> {
-        self.pop_front_node().map(Node::into_element)
+    /// Makes `node`, whose predecessor is `prev`, the new head by joining the tail to the old
+    /// head and cutting the list between `prev` and `node`.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list and `prev` the node before it.
+    unsafe fn rotate_at(&mut self, prev: NonNull<Node<E>>, node: NonNull<Node<E>>) {
+        self.invalidate_hash();
+        let (head, tail) = (self.head.unwrap(), self.tail.unwrap());
+        (*tail.as_ptr()).xor_assign(Some(head));
+        (*head.as_ptr()).xor_assign(Some(tail));
+        (*prev.as_ptr()).xor_assign(Some(node));
+        (*node.as_ptr()).xor_assign(Some(prev));
+        self.head = Some(node);
+        self.tail = Some(prev);
     }
 
-    pub fn push_back(&mut self, elem: E) {
-        self.push_back_node(Box::new(Node::new(elem)));
+    /// Walks the list once, unlinking and dropping every element for which `f` returns `false`.
+    /// Returns the number of removed elements.
+    fn retain_nodes<F: FnMut(&mut E) -> bool>(&mut self, mut f: F) -> usize {
+        let old_len = self.len;
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).xor(prev);
+                if f(&mut (*node.as_ptr()).element) {
+                    prev = Some(node);
+                } else {
+                    drop(self.unlink_node(prev, node));
+                }
+            }
+        }
+        old_len - self.len
     }
 
-    pub fn pop_back(&mut self) -> Option<E> {
-        self.pop_back_node().map(Node::into_element)
+    /// Moves all nodes into a new list, leaving `self` empty.
+    fn take_all(&mut self) -> Self {
+        #[cfg(feature = "cached_hash")]
+        if let Some(cache) = &mut self.hash_cache {
+            cache.clear();
+        }
+        LinkedList {
+            head: self.head.take(),
+            tail: self.tail.take(),
+            len: mem::replace(&mut self.len, 0),
+            ..Self::new()
+        }
     }
 
-    pub fn append(&mut self, other: &mut Self) {
-        match self.tail {
-            None => mem::swap(self, other),
-            Some(mut tail) => {
-                // `as_mut` is okay here becaute we have exclusive access to the
-                // entirety of both lists.
-                if let Some(mut other_head) = other.head.take() {
-                    unsafe {
-                        tail.as_mut().xor_assign(Some(other_head));
-                        other_head.as_mut().xor_assign(Some(tail));
-                    }
+    /// Swaps the nodes, and any cached hash describing them, with `other`. Capacity limits stay.
+    fn swap_contents(&mut self, other: &mut Self) {
+        mem::swap(&mut self.head, &mut other.head);
+        mem::swap(&mut self.tail, &mut other.tail);
+        mem::swap(&mut self.len, &mut other.len);
+        #[cfg(feature = "cached_hash")]
+        mem::swap(&mut self.hash_cache, &mut other.hash_cache);
+    }
 
-                    self.tail = other.tail.take();
-                    self.len += mem::replace(&mut other.len, 0);
-                }
+    /// Detaches the first `at` nodes into a new list. `at` must not exceed `len`.
+    fn split_front(&mut self, at: usize) -> Self {
+        debug_assert!(at <= self.len);
+        if at == self.len {
+            return self.take_all();
+        } else if at == 0 {
+            return Self::new();
+        }
+
+        unsafe {
+            let mut prev = None;
+            let mut last = self.head.unwrap();
+            for _ in 1..at {
+                let next = (*last.as_ptr()).xor(prev).unwrap();
+                prev = Some(last);
+                last = next;
             }
+            let first = (*last.as_ptr()).xor(prev).unwrap();
+            self.split_between(last, first, at)
         }
     }
-    pub fn iter(&self) -> Iter<'_, E> {
-        Iter {
+
+    /// Cuts the list between the adjacent nodes `last` and `first` and returns the front part.
+    ///
+    /// # Safety
+    ///
+    /// `last` and `first` must be adjacent nodes of this list, and `at` the number of nodes up to
+    /// and including `last`.
+    unsafe fn split_between(
+        &mut self,
+        last: NonNull<Node<E>>,
+        first: NonNull<Node<E>>,
+        at: usize,
+    ) -> Self {
+        self.invalidate_hash();
+        (*last.as_ptr()).xor_assign(Some(first));
+        (*first.as_ptr()).xor_assign(Some(last));
+
+        let front = LinkedList {
             head: self.head,
-            prev_head: None,
-            tail: self.tail,
-            prev_tail: None,
-            len: self.len,
-            marker: PhantomData,
-        }
+            tail: Some(last),
+            len: at,
+            ..Self::new()
+        };
+        self.head = Some(first);
+        self.len -= at;
+        front
     }
-}
 
-impl<E> Default for LinkedList<E> {
-    fn default() -> Self {
-        Self::new()
+    /// Detaches the last `at` nodes into a new list. `at` must not exceed `len`.
+    fn split_back(&mut self, at: usize) -> Self {
+        // The links don't encode a direction, so with the ends swapped the list reads back to front.
+        mem::swap(&mut self.head, &mut self.tail);
+        let mut back = self.split_front(at);
+        mem::swap(&mut self.head, &mut self.tail);
+        mem::swap(&mut back.head, &mut back.tail);
+        back
     }
-}
 
-impl<E> FromIterator<E> for LinkedList<E> {
-    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
-        let mut list = Self::new();
-        list.extend(iter);
-        list
-    }
-}
+    fn pop_back_node(&mut self) -> Option<Box<Node<E>>> {
+        self.tail.map(|node_ptr| unsafe {
+            let mut node = Box::from_raw(node_ptr.as_ptr());
+            self.tail = node.xor(None);
+            node.prev_x_next = 0;
 
-impl<E> Extend<E> for LinkedList<E> {
-    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
-        iter.into_iter().for_each(move |elem| self.push_back(elem));
+            match self.tail {
+                None => self.head = None,
+                Some(tail) => (*tail.as_ptr()).xor_assign(Some(node_ptr)),
+            }
+            self.len -= 1;
+            #[cfg(feature = "cached_hash")]
+            if let Some(cache) = &mut self.hash_cache {
+                cache.pop_back(&node.element);
+            }
+            node
+        })
     }
 }
 
-impl<E: PartialEq> PartialEq for LinkedList<E> {
-    fn eq(&self, other: &Self) -> bool {
-        self.len() == other.len() && self.iter().eq(other)
+#[cfg(feature = "alloc")]
+impl<E> LinkedList<E> {
+    pub fn new() -> Self {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            limit: None,
+            #[cfg(feature = "cached_hash")]
+            hash_cache: None,
+            phantom: PhantomData,
+        }
     }
 
-    fn ne(&self, other: &Self) -> bool {
-        self.len() != other.len() || self.iter().ne(other)
+    /// Creates an empty list that never holds more than `cap` elements.
+    ///
+    /// Pushing past the limit through `push_front`, `push_back`, `extend` or `append` panics,
+    /// use `try_push_back` or `push_back_evicting` to handle a full list instead.
+    pub fn with_capacity_limit(cap: usize) -> Self {
+        LinkedList {
+            limit: Some(cap),
+            ..Self::new()
+        }
     }
-}
 
-impl<E: Eq> Eq for LinkedList<E> {}
-
-impl<E: PartialOrd> PartialOrd for LinkedList<E> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.iter().partial_cmp(other)
+    /// Creates a list of `n` clones of `elem`. `elem` itself becomes the last element.
+    pub fn from_elem(elem: E, n: usize) -> Self
+    where
+        E: Clone,
+    {
+        let mut list = Self::new();
+        if n > 0 {
+            for _ in 1..n {
+                list.push_back(elem.clone());
+            }
+            list.push_back(elem);
+        }
+        list
     }
-}
 
-impl<E: Ord> Ord for LinkedList<E> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.iter().cmp(other)
+    /// Creates a list of `n` elements returned by calling `f` repeatedly, front to back.
+    pub fn repeat_with<F: FnMut() -> E>(n: usize, mut f: F) -> Self {
+        let mut list = Self::new();
+        for _ in 0..n {
+            list.push_back(f());
+        }
+        list
     }
-}
 
-impl<E: Clone> Clone for LinkedList<E> {
-    fn clone(&self) -> Self {
-        self.iter().cloned().collect()
+    /// Returns the capacity limit set by `with_capacity_limit`, if any.
+    pub fn capacity_limit(&self) -> Option<usize> {
+        self.limit
     }
-    // TODO: fn clone_from
-}
 
-impl<E: fmt::Debug> fmt::Debug for LinkedList<E> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self).finish()
+    pub fn len(&self) -> usize {
+        self.len
     }
-}
 
-impl<E: Hash> Hash for LinkedList<E> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.len().hash(state);
-        for elt in self {
-            elt.hash(state);
-        }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
-}
 
-impl<E> Drop for LinkedList<E> {
-    fn drop(&mut self) {
+    /// Removes and drops all elements, one node at a time.
+    pub fn clear(&mut self) {
         struct DropGuard<'a, E>(&'a mut LinkedList<E>);
 
         impl<'a, E> Drop for DropGuard<'a, E> {
@@ -217,115 +560,1771 @@ impl<E> Drop for LinkedList<E> {
             }
         }
 
+        // Don't update the cached hash for every node, reset it once the list is empty instead.
+        #[cfg(feature = "cached_hash")]
+        let cache = self.hash_cache.take();
+
         while let Some(node) = self.pop_front_node() {
             let guard = DropGuard(self);
             drop(node);
             mem::forget(guard);
         }
-    }
-}
 
-unsafe impl<E: Send> Send for LinkedList<E> {}
-unsafe impl<E: Sync> Sync for LinkedList<E> {}
+        #[cfg(feature = "cached_hash")]
+        {
+            self.hash_cache = cache.map(|mut cache| {
+                cache.clear();
+                cache
+            });
+        }
+    }
 
-unsafe impl<E: Send> Send for Iter<'_, E> {}
-unsafe impl<E: Sync> Sync for Iter<'_, E> {}
+    /// Returns the number of heap bytes taken up by the list's nodes.
+    pub fn memory_usage(&self) -> usize {
+        self.len * mem::size_of::<Node<E>>()
+    }
 
-#[derive(Debug)]
-struct Node<E> {
-    prev_x_next: usize,
-    element: E,
-}
+    /// Returns a reference to the front element, or `None` if the list is empty.
+    pub fn front(&self) -> Option<&E> {
+        unsafe { self.head.as_ref().map(|node| &node.as_ref().element) }
+    }
 
-impl<E> Node<E> {
-    fn new(element: E) -> Self {
-        Node {
-            prev_x_next: 0,
-            element,
-        }
+    /// Returns a reference to the back element, or `None` if the list is empty.
+    pub fn back(&self) -> Option<&E> {
+        unsafe { self.tail.as_ref().map(|node| &node.as_ref().element) }
     }
 
-    fn xor(&self, other: Option<NonNull<Self>>) -> Option<NonNull<Self>> {
-        let other = other.map(|nn| nn.as_ptr() as usize).unwrap_or(0);
-        let result = other ^ self.prev_x_next;
-        NonNull::new(result as *mut _)
+    /// Returns a mutable reference to the front element, or `None` if the list is empty.
+    pub fn front_mut(&mut self) -> Option<&mut E> {
+        self.invalidate_hash();
+        unsafe { self.head.as_mut().map(|node| &mut node.as_mut().element) }
     }
 
-    fn xor_assign(&mut self, other: Option<NonNull<Self>>) {
-        let other = other.map(|nn| nn.as_ptr() as usize).unwrap_or(0);
-        self.prev_x_next ^= other;
+    /// Returns a mutable reference to the back element, or `None` if the list is empty.
+    pub fn back_mut(&mut self) -> Option<&mut E> {
+        self.invalidate_hash();
+        unsafe { self.tail.as_mut().map(|node| &mut node.as_mut().element) }
     }
 
-    fn into_element(self: Box<Self>) -> E {
-        self.element
+    /// Same as `front`, named after `slice::first` so that code written against slices ports
+    /// over unchanged.
+    pub fn first(&self) -> Option<&E> {
+        self.front()
     }
-}
 
-pub struct Iter<'a, E: 'a> {
-    head: Option<NonNull<Node<E>>>,
-    prev_head: Option<NonNull<Node<E>>>,
-    tail: Option<NonNull<Node<E>>>,
-    prev_tail: Option<NonNull<Node<E>>>,
-    len: usize,
-    marker: PhantomData<&'a Node<E>>,
-}
+    /// Same as `back`, named after `slice::last` so that code written against slices ports over
+    /// unchanged.
+    pub fn last(&self) -> Option<&E> {
+        self.back()
+    }
 
-impl<E: fmt::Debug> fmt::Debug for Iter<'_, E> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let head = self.head.clone();
-        let tail = self.tail.clone();
-        head.map(|nn| unsafe {
-            (&mut *nn.as_ptr()).xor_assign(self.prev_head);
-        });
-        tail.map(|nn| unsafe {
-            (&mut *nn.as_ptr()).xor_assign(self.prev_tail);
-        });
+    /// Returns whether the list contains an element equal to `x`.
+    pub fn contains(&self, x: &E) -> bool
+    where
+        E: PartialEq,
+    {
+        self.iter().any(|e| e == x)
+    }
 
-        f.debug_tuple("Iter")
-            .field(&*mem::ManuallyDrop::new(LinkedList {
-                head,
-                tail,
-                len: self.len,
-                phantom: PhantomData,
-            }))
-            .field(&self.len)
-            .finish()
+    /// Returns the middle element, or the lower of the two middle elements if the list has an
+    /// even length. Walks the list with a slow and a fast iterator instead of relying on `len`.
+    pub fn find_middle(&self) -> Option<&E> {
+        let mut slow = self.iter();
+        let mut fast = self.iter().skip(1);
+        let mut middle = slow.next();
+        while fast.next().is_some() && fast.next().is_some() {
+            middle = slow.next();
+        }
+        middle
     }
-}
 
-impl<T> Clone for Iter<'_, T> {
-    fn clone(&self) -> Self {
-        Iter { ..*self }
+    /// Returns a clone of the list in reverse order, built in a single walk from the back. Like
+    /// `clone`, it keeps the capacity limit.
+    pub fn clone_reversed(&self) -> Self
+    where
+        E: Clone,
+    {
+        let mut list = LinkedList {
+            limit: self.limit,
+            ..Self::new()
+        };
+        list.extend(self.iter_rev().cloned());
+        list
     }
-}
 
-impl<'a, E> Iterator for Iter<'a, E> {
-    type Item = &'a E;
+    /// Calls `f` on each element from front to back, stopping at and returning the first error.
+    pub fn try_for_each<F, R>(&self, f: F) -> Result<(), R>
+    where
+        F: FnMut(&E) -> Result<(), R>,
+    {
+        self.iter().try_for_each(f)
+    }
 
-    fn next(&mut self) -> Option<&'a E> {
-        if self.len == 0 {
-            None
-        } else {
-            self.head.map(|node| unsafe {
-                let node = &*node.as_ptr();
-                self.len -= 1;
-                self.head = node.xor(self.prev_head);
-                self.prev_head = Some(node.into());
-                &node.element
-            })
+    /// Searches from the back for an element matching `pred` and returns its index, counted from
+    /// the front.
+    pub fn rposition<F: FnMut(&E) -> bool>(&self, mut pred: F) -> Option<usize> {
+        let mut index = self.len;
+        for elem in self.iter().rev() {
+            index -= 1;
+            if pred(elem) {
+                return Some(index);
+            }
         }
+        None
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    /// Returns the smallest element, or the first one of several equally small elements.
+    ///
+    /// Not called `min` because `list.min()` would resolve to `Ord::min`.
+    pub fn min_element(&self) -> Option<&E>
+    where
+        E: Ord,
+    {
+        self.iter()
+            .reduce(|min, elem| if elem < min { elem } else { min })
     }
 
-    fn last(mut self) -> Option<&'a E> {
-        self.next_back()
+    /// Returns the largest element, or the first one of several equally large elements.
+    ///
+    /// Not called `max` because `list.max()` would resolve to `Ord::max`.
+    pub fn max_element(&self) -> Option<&E>
+    where
+        E: Ord,
+    {
+        self.iter()
+            .reduce(|max, elem| if elem > max { elem } else { max })
     }
-}
 
-impl<'a, E> DoubleEndedIterator for Iter<'a, E> {
+    /// Returns whether the elements are sorted in ascending order.
+    pub fn is_sorted(&self) -> bool
+    where
+        E: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Returns whether `f` returns `true` for every pair of adjacent elements, i.e. whether the
+    /// list is sorted by the order `f` describes.
+    pub fn is_sorted_by<F: FnMut(&E, &E) -> bool>(&self, mut f: F) -> bool {
+        self.pairs().all(|(a, b)| f(a, b))
+    }
+
+    /// Returns whether the keys `f` returns are sorted in ascending order. Stops at the first
+    /// pair out of order.
+    pub fn is_sorted_by_key<K: PartialOrd, F: FnMut(&E) -> K>(&self, f: F) -> bool {
+        let mut keys = self.iter().map(f);
+        let mut prev = match keys.next() {
+            Some(key) => key,
+            None => return true,
+        };
+        for key in keys {
+            match prev.partial_cmp(&key) {
+                Some(Ordering::Less) | Some(Ordering::Equal) => {}
+                Some(Ordering::Greater) | None => return false,
+            }
+            prev = key;
+        }
+        true
+    }
+
+    /// Maps every window of `size` consecutive elements to a new element, from front to back.
+    /// Returns an empty list if the list is shorter than `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn window_map<B, F: FnMut(&[&E]) -> B>(&self, size: usize, mut f: F) -> LinkedList<B> {
+        assert!(size != 0, "window size must be non-zero");
+        let mut windows = LinkedList::new();
+        // The current window is always the last `size` entries. Shifting the buffer only once it
+        // holds two windows keeps each step amortized O(1).
+        let mut buf = Vec::with_capacity(2 * size);
+        for elem in self {
+            if buf.len() == 2 * size {
+                buf.drain(..size);
+            }
+            buf.push(elem);
+            if buf.len() >= size {
+                windows.push_back(f(&buf[buf.len() - size..]));
+            }
+        }
+        windows
+    }
+
+    /// Returns whether both lists contain the same elements, in any order.
+    pub fn eq_ignore_order(&self, other: &Self) -> bool
+    where
+        E: Ord,
+    {
+        if self.len != other.len {
+            return false;
+        }
+        let mut ours: Vec<&E> = self.iter().collect();
+        let mut theirs: Vec<&E> = other.iter().collect();
+        ours.sort_unstable();
+        theirs.sort_unstable();
+        ours == theirs
+    }
+
+    /// Returns mutable references to the elements at all of the given `indices` at once, or
+    /// `None` if any index is out of bounds or appears more than once.
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut E; N]> {
+        for (i, &index) in indices.iter().enumerate() {
+            if index >= self.len || indices[..i].contains(&index) {
+                return None;
+            }
+        }
+        self.invalidate_hash();
+
+        let end = indices.iter().max().map_or(0, |&max| max + 1);
+        let mut nodes = [None; N];
+        let mut prev = None;
+        let mut cur = self.head;
+        for pos in 0..end {
+            let node = cur.unwrap();
+            for (slot, &index) in nodes.iter_mut().zip(&indices) {
+                if index == pos {
+                    *slot = Some(node);
+                }
+            }
+            cur = unsafe { (*node.as_ptr()).xor(prev) };
+            prev = Some(node);
+        }
+        // The indices are distinct, so these references don't alias.
+        Some(nodes.map(|node| unsafe { &mut (*node.unwrap().as_ptr()).element }))
+    }
+
+    /// Clones elements from the front of the list into `out` until either runs out, and returns
+    /// the number of elements written.
+    pub fn copy_into_slice(&self, out: &mut [E]) -> usize
+    where
+        E: Clone,
+    {
+        let mut written = 0;
+        for (slot, elem) in out.iter_mut().zip(self) {
+            slot.clone_from(elem);
+            written += 1;
+        }
+        written
+    }
+
+    /// Returns the element at `index`, walking from whichever end is closer, or `None` if `index`
+    /// is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&E> {
+        if index >= self.len {
+            return None;
+        }
+        let (_, node) = self.node_at(index);
+        Some(unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Returns the element at `index` mutably, walking from whichever end is closer, or `None` if
+    /// `index` is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut E> {
+        if index >= self.len {
+            return None;
+        }
+        self.invalidate_hash();
+        let (_, node) = self.node_at(index);
+        Some(unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Swaps the elements at `i` and `j`. Walks to the nearer index from the nearer end, and on
+    /// from there to the other one unless that is closer to the back, so adjacent indices cost a
+    /// single extra step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let (lo, hi) = if i <= j { (i, j) } else { (j, i) };
+        assert!(
+            hi < self.len,
+            "Cannot swap index {} in a list of length {}",
+            hi,
+            self.len
+        );
+        if lo == hi {
+            return;
+        }
+        self.invalidate_hash();
+        let (mut prev, a) = self.node_at(lo);
+        unsafe {
+            let b = if hi - lo <= self.len - 1 - hi {
+                let mut node = a;
+                for _ in lo..hi {
+                    let next = (*node.as_ptr()).xor(prev).unwrap();
+                    prev = Some(node);
+                    node = next;
+                }
+                node
+            } else {
+                self.node_at(hi).1
+            };
+            ptr::swap(&mut (*a.as_ptr()).element, &mut (*b.as_ptr()).element);
+        }
+    }
+
+    /// Replaces the element at `index` with `value` and returns the old element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn replace(&mut self, index: usize, value: E) -> E {
+        assert!(
+            index < self.len,
+            "Cannot replace at an index outside the list bounds"
+        );
+        self.invalidate_hash();
+        let (_, node) = self.node_at(index);
+        unsafe { mem::replace(&mut (*node.as_ptr()).element, value) }
+    }
+
+    pub fn push_front(&mut self, elem: E) {
+        self.push_front_node(Box::new(Node::new(elem)));
+    }
+
+    pub fn pop_front(&mut self) -> Option<E> {
+        self.pop_front_node().map(Node::into_element)
+    }
+
+    pub fn push_back(&mut self, elem: E) {
+        self.push_back_node(Box::new(Node::new(elem)));
+    }
+
+    /// Inserts `elem` at `index`, walking from whichever end is closer to link it in between its
+    /// new neighbours.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len` or the list is at its capacity limit.
+    pub fn insert(&mut self, index: usize, elem: E) {
+        assert!(
+            index <= self.len,
+            "Cannot insert at index {} into a list of length {}",
+            index,
+            self.len
+        );
+        let node = Box::new(Node::new(elem));
+        if index == self.len {
+            self.push_back_node(node);
+        } else {
+            let (prev, next) = self.node_at(index);
+            unsafe { self.link_node(prev, Some(next), node) };
+        }
+    }
+
+    /// Removes and returns the element at `at`, walking from whichever end is closer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at >= len`, see `pop_nth` for a non-panicking version.
+    pub fn remove(&mut self, at: usize) -> E {
+        let len = self.len;
+        match self.pop_nth(at) {
+            Some(elem) => elem,
+            None => panic!(
+                "Cannot remove at index {} from a list of length {}",
+                at, len
+            ),
+        }
+    }
+
+    /// Removes and returns the element at `index`, or returns `None` if `index` is out of bounds.
+    pub fn pop_nth(&mut self, index: usize) -> Option<E> {
+        if index >= self.len {
+            return None;
+        }
+        let (prev, node) = self.node_at(index);
+        Some(unsafe { self.unlink_node(prev, node) }.into_element())
+    }
+
+    /// Removes and returns the first element equal to `x`, or returns `None` if there is none.
+    /// Only the matching node is unlinked, the rest of the list stays in place.
+    pub fn remove_item(&mut self, x: &E) -> Option<E>
+    where
+        E: PartialEq,
+    {
+        let (prev, node) = self.find_node(|elem| elem == x)?;
+        Some(unsafe { self.unlink_node(prev, node) }.into_element())
+    }
+
+    /// Shortens the list to its first `new_len` elements by dropping elements from the back. Has
+    /// no effect if the list is already at most `new_len` long.
+    ///
+    /// The excess elements are detached in one piece, walking from whichever end is closer.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len {
+            drop(self.split_off(new_len));
+        }
+    }
+
+    /// Resizes the list to `new_len` elements, truncating it from the back or pushing elements
+    /// returned by `f` to the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` exceeds the capacity limit.
+    pub fn resize_with<F: FnMut() -> E>(&mut self, new_len: usize, mut f: F) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+        } else {
+            self.check_limit(new_len - self.len);
+            while self.len < new_len {
+                self.push_back(f());
+            }
+        }
+    }
+
+    /// Shortens the list to its last `new_len` elements by dropping elements from the front. Has
+    /// no effect if the list is already at most `new_len` long.
+    ///
+    /// The excess elements are detached in one piece, walking from whichever end is closer.
+    pub fn truncate_front(&mut self, new_len: usize) {
+        if new_len >= self.len {
+            return;
+        }
+        let excess = self.len - new_len;
+        let front = if excess <= self.len / 2 {
+            self.split_front(excess)
+        } else {
+            let mut front = self.split_back(new_len);
+            self.swap_contents(&mut front);
+            front
+        };
+        drop(front);
+    }
+
+    /// Removes and returns the front element if `pred` returns `true` for it, otherwise leaves
+    /// the list unchanged.
+    pub fn pop_front_if<F: FnOnce(&E) -> bool>(&mut self, pred: F) -> Option<E> {
+        if pred(self.front()?) {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Removes and returns the back element if `pred` returns `true` for it, otherwise leaves
+    /// the list unchanged.
+    pub fn pop_back_if<F: FnOnce(&E) -> bool>(&mut self, pred: F) -> Option<E> {
+        if pred(self.back()?) {
+            self.pop_back()
+        } else {
+            None
+        }
+    }
+
+    /// Drops elements from both ends for as long as they match `f`, keeping everything from the
+    /// first to the last non-matching element.
+    pub fn trim<F: FnMut(&E) -> bool>(&mut self, mut f: F) {
+        while self.pop_front_if(&mut f).is_some() {}
+        while self.pop_back_if(&mut f).is_some() {}
+    }
+
+    /// Returns an iterator that removes and yields elements from the front for as long as `pred`
+    /// returns `true`. The first element that doesn't match stays in the list.
+    ///
+    /// Elements are removed as they are yielded, so dropping the iterator early leaves the rest of
+    /// the list in place.
+    pub fn drain_while<F: FnMut(&E) -> bool>(&mut self, pred: F) -> DrainWhile<'_, E, F> {
+        DrainWhile {
+            list: self,
+            pred,
+            done: false,
+        }
+    }
+
+    /// Appends `elem` to the back of the list, or hands it back if the list is at its capacity
+    /// limit.
+    pub fn try_push_back(&mut self, elem: E) -> Result<(), E> {
+        if self.is_full() {
+            Err(elem)
+        } else {
+            self.push_back(elem);
+            Ok(())
+        }
+    }
+
+    /// Appends `elem` to the back of the list, popping the front element first if the list is at
+    /// its capacity limit. Returns the evicted element.
+    ///
+    /// With a capacity limit of zero `elem` itself is returned.
+    pub fn push_back_evicting(&mut self, elem: E) -> Option<E> {
+        if !self.is_full() {
+            self.push_back(elem);
+            None
+        } else if self.len == 0 {
+            Some(elem)
+        } else {
+            let evicted = self.pop_front();
+            self.push_back(elem);
+            evicted
+        }
+    }
+
+    pub fn pop_back(&mut self) -> Option<E> {
+        self.pop_back_node().map(Node::into_element)
+    }
+
+    pub fn append(&mut self, other: &mut Self) {
+        self.check_limit(other.len);
+        #[cfg(feature = "cached_hash")]
+        {
+            if let Some(cache) = &mut self.hash_cache {
+                cache.append(other);
+            }
+            if let Some(cache) = &mut other.hash_cache {
+                cache.clear();
+            }
+        }
+        match self.tail {
+            None => {
+                self.head = other.head.take();
+                self.tail = other.tail.take();
+                self.len = mem::replace(&mut other.len, 0);
+            }
+            Some(mut tail) => {
+                // `as_mut` is okay here becaute we have exclusive access to the
+                // entirety of both lists.
+                if let Some(mut other_head) = other.head.take() {
+                    unsafe {
+                        tail.as_mut().xor_assign(Some(other_head));
+                        other_head.as_mut().xor_assign(Some(tail));
+                    }
+
+                    self.tail = other.tail.take();
+                    self.len += mem::replace(&mut other.len, 0);
+                }
+            }
+        }
+    }
+
+    /// Moves up to `n` elements from the front of `other` to the back of the list and returns
+    /// how many were moved. The elements are detached and relinked as one chain.
+    pub fn append_n(&mut self, other: &mut Self, n: usize) -> usize {
+        let n = n.min(other.len);
+        self.check_limit(n);
+        self.append(&mut other.split_front(n));
+        n
+    }
+
+    /// Moves all elements of `other` to the front of the list in O(1), leaving `other` empty.
+    pub fn append_front(&mut self, other: &mut Self) {
+        self.check_limit(other.len);
+        #[cfg(feature = "cached_hash")]
+        {
+            if let Some(cache) = &mut self.hash_cache {
+                cache.prepend(other);
+            }
+            if let Some(cache) = &mut other.hash_cache {
+                cache.clear();
+            }
+        }
+        match self.head {
+            None => {
+                self.head = other.head.take();
+                self.tail = other.tail.take();
+                self.len = mem::replace(&mut other.len, 0);
+            }
+            Some(mut head) => {
+                if let Some(mut other_tail) = other.tail.take() {
+                    unsafe {
+                        head.as_mut().xor_assign(Some(other_tail));
+                        other_tail.as_mut().xor_assign(Some(head));
+                    }
+
+                    self.head = other.head.take();
+                    self.len += mem::replace(&mut other.len, 0);
+                }
+            }
+        }
+    }
+
+    /// Same as `append_front`, named as the counterpart of `append`.
+    pub fn prepend(&mut self, other: &mut Self) {
+        self.append_front(other);
+    }
+
+    /// Removes the elements in `range` and returns them as an iterator. The range is detached
+    /// and the remaining halves relinked right away, so the list is whole again even if the
+    /// iterator is dropped before it's used up. Any elements left in the iterator are dropped
+    /// with it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the end of the list.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, E> {
+        let (start, end) = self.range_indices(range);
+        let mut drained = self.split_off(start);
+        let mut rest = drained.split_off(end - start);
+        self.append(&mut rest);
+        Drain {
+            iter: drained.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Replaces the elements in `range` with the items of `replace_with` and returns the removed
+    /// elements as an iterator. The new elements are linked in where the old ones were, nothing
+    /// else in the list moves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the end of the list, or if the new
+    /// elements would exceed the capacity limit. The list is left unchanged in that case.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Drain<'_, E>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = E>,
+    {
+        let (start, end) = self.range_indices(range);
+        let mut replacement: Self = replace_with.into_iter().collect();
+        self.check_limit(replacement.len.saturating_sub(end - start));
+        let mut drained = self.split_off(start);
+        let mut rest = drained.split_off(end - start);
+        self.append(&mut replacement);
+        self.append(&mut rest);
+        Drain {
+            iter: drained.into_iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Retains only the elements for which `f` returns `true`, unlinking the others in a single
+    /// walk from front to back.
+    pub fn retain<F: FnMut(&E) -> bool>(&mut self, mut f: F) {
+        self.retain_nodes(|elem| f(elem));
+    }
+
+    /// Like `retain`, but `f` may also modify the elements it visits.
+    pub fn retain_mut<F: FnMut(&mut E) -> bool>(&mut self, f: F) {
+        self.invalidate_hash();
+        self.retain_nodes(f);
+    }
+
+    /// Returns an iterator that walks the list from front to back, and unlinks and yields every
+    /// element for which `filter` returns `true`.
+    ///
+    /// Elements are only removed as they are yielded. If the iterator is dropped early, or
+    /// `filter` panics, the elements it hasn't yielded stay in the list.
+    pub fn extract_if<F: FnMut(&mut E) -> bool>(&mut self, filter: F) -> ExtractIf<'_, E, F> {
+        self.invalidate_hash();
+        ExtractIf {
+            prev: None,
+            cur: self.head,
+            remaining: self.len,
+            list: self,
+            filter,
+        }
+    }
+
+    /// Removes consecutive equal elements, keeping the first of each run. See `dedup_by`.
+    pub fn dedup(&mut self)
+    where
+        E: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive elements that map to the same key, keeping the first of each run. See
+    /// `dedup_by`.
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut E) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive elements for which `same_bucket` returns `true`, in one pass from
+    /// front to back. `same_bucket` is passed each element and the last element kept before it,
+    /// in that order, and the element is unlinked and dropped if it returns `true`.
+    pub fn dedup_by<F: FnMut(&mut E, &mut E) -> bool>(&mut self, mut same_bucket: F) {
+        self.invalidate_hash();
+        let mut kept = match self.head {
+            Some(head) => head,
+            None => return,
+        };
+        unsafe {
+            let mut cur = (*kept.as_ptr()).xor(None);
+            while let Some(node) = cur {
+                cur = (*node.as_ptr()).xor(Some(kept));
+                if same_bucket(&mut (*node.as_ptr()).element, &mut (*kept.as_ptr()).element) {
+                    drop(self.unlink_node(Some(kept), node));
+                } else {
+                    kept = node;
+                }
+            }
+        }
+    }
+
+    /// Retains only the elements for which `f` returns `true` and returns how many were removed.
+    pub fn retain_count<F: FnMut(&E) -> bool>(&mut self, mut f: F) -> usize {
+        self.retain_nodes(|elem| f(elem))
+    }
+
+    /// Removes every element for which `pred` returns `true`, unlinking them in a single walk,
+    /// and returns how many were removed.
+    pub fn remove_all<F: FnMut(&E) -> bool>(&mut self, mut pred: F) -> usize {
+        self.retain_nodes(|elem| !pred(elem))
+    }
+
+    /// Retains only the elements for which `f` returns `true`. `f` also receives each element's
+    /// index from before any removals.
+    pub fn retain_with_index<F: FnMut(usize, &E) -> bool>(&mut self, mut f: F) {
+        let mut index = 0;
+        self.retain_nodes(|elem| {
+            index += 1;
+            f(index - 1, elem)
+        });
+    }
+
+    /// Reverses the order of the elements in O(1).
+    ///
+    /// Nodes store the xor of both neighbours, which reads the same in either direction, so only
+    /// the head and tail need to be swapped.
+    pub fn reverse(&mut self) {
+        self.invalidate_hash();
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Sorts the list, keeping equal elements in order. See `sort_by`.
+    pub fn sort(&mut self)
+    where
+        E: Ord,
+    {
+        self.sort_by(E::cmp);
+    }
+
+    /// Sorts the list by the keys `f` returns, keeping elements with equal keys in order. See
+    /// `sort_by`.
+    pub fn sort_by_key<K: Ord, F: FnMut(&E) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Sorts the list by the keys `f` returns, keeping elements with equal keys in order. Unlike
+    /// `sort_by_key`, `f` is only called once per element, which pays off for expensive keys.
+    ///
+    /// The keys are sorted in a `Vec` next to pointers to their nodes, which are then relinked
+    /// in that order. If `f` or the key comparison panics, the list is left unchanged.
+    pub fn sort_by_cached_key<K: Ord, F: FnMut(&E) -> K>(&mut self, mut f: F) {
+        let mut keyed = Vec::with_capacity(self.len);
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                keyed.push((f(&(*node.as_ptr()).element), node));
+                cur = (*node.as_ptr()).xor(prev);
+            }
+            prev = Some(node);
+        }
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+        self.invalidate_hash();
+        self.head = None;
+        self.tail = None;
+        self.len = 0;
+        for (_, node) in keyed {
+            unsafe {
+                let mut node = Box::from_raw(node.as_ptr());
+                node.prev_x_next = 0;
+                self.push_back_node(node);
+            }
+        }
+    }
+
+    /// Sorts the list with a comparison function, keeping equal elements in order.
+    ///
+    /// This is a bottom-up merge sort that relinks the nodes, so the elements themselves are
+    /// never moved or cloned and nothing is allocated. It takes O(n log n) comparisons. If
+    /// `compare` panics, the list is left in some unspecified order, but no element is lost.
+    pub fn sort_by<F: FnMut(&E, &E) -> Ordering>(&mut self, mut compare: F) {
+        self.invalidate_hash();
+        let mut width = 1;
+        while width < self.len {
+            // The runs still to be merged wait in `pending.left`, so a panic puts them back
+            // behind the ones merged so far.
+            let mut pending = Merge {
+                left: self.take_all(),
+                right: Self::new(),
+                out: self,
+            };
+            while !pending.left.is_empty() {
+                let left = pending.left.split_front(width.min(pending.left.len));
+                let right = pending.left.split_front(width.min(pending.left.len));
+                Merge {
+                    out: pending.out,
+                    left,
+                    right,
+                }
+                .run(&mut compare);
+            }
+            width *= 2;
+        }
+    }
+
+    /// Merges the sorted list `other` into this sorted list, keeping the result sorted. See
+    /// `merge_by`.
+    pub fn merge(&mut self, other: Self)
+    where
+        E: Ord,
+    {
+        self.merge_by(other, E::cmp);
+    }
+
+    /// Merges the list `other` into this list in a single pass, both sorted by `compare`, keeping
+    /// the result sorted. The nodes of `other` are relinked, nothing is allocated, and elements
+    /// of `self` stay in front of equal elements of `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the merged list would exceed the capacity limit.
+    pub fn merge_by<F: FnMut(&E, &E) -> Ordering>(&mut self, other: Self, mut compare: F) {
+        self.check_limit(other.len);
+        Merge {
+            left: self.take_all(),
+            right: other,
+            out: self,
+        }
+        .run(&mut compare);
+    }
+
+    /// Inserts `elem` into this sorted list at its ordered position, after any equal elements.
+    /// See `insert_sorted_by`.
+    pub fn insert_sorted(&mut self, elem: E)
+    where
+        E: Ord,
+    {
+        self.insert_sorted_by(elem, E::cmp);
+    }
+
+    /// Inserts `elem` into this list, sorted by `compare`, in front of the first element that
+    /// compares greater than it. Scans from the front and links in a single node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is at its capacity limit.
+    pub fn insert_sorted_by<F: FnMut(&E, &E) -> Ordering>(&mut self, elem: E, mut compare: F) {
+        let node = Box::new(Node::new(elem));
+        match self.find_node(|e| compare(e, &node.element) == Ordering::Greater) {
+            Some((prev, next)) => unsafe {
+                self.link_node(prev, Some(next), node);
+            },
+            None => self.push_back_node(node),
+        }
+    }
+
+    /// Rotates the list so that the first element for which `pred` returns `true` becomes the
+    /// front. Returns `false`, leaving the list unchanged, if there is no such element.
+    pub fn rotate_to<F: FnMut(&E) -> bool>(&mut self, pred: F) -> bool {
+        match self.find_node(pred) {
+            Some((prev, node)) => {
+                if let Some(prev) = prev {
+                    unsafe { self.rotate_at(prev, node) };
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the first element equal to `value` to the front of the list by relinking its node.
+    /// Returns `false` if there is no such element.
+    pub fn bring_to_front(&mut self, value: &E) -> bool
+    where
+        E: PartialEq,
+    {
+        match self.find_node(|elem| elem == value) {
+            Some((prev, node)) => {
+                if prev.is_some() {
+                    let node = unsafe { self.unlink_node(prev, node) };
+                    self.push_front_node(node);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the back element to the front in O(1), by relinking its node.
+    pub fn rotate_right_one(&mut self) {
+        if self.len > 1 {
+            let node = self.pop_back_node().unwrap();
+            self.push_front_node(node);
+        }
+    }
+
+    /// Moves the front element to the back in O(1), by relinking its node.
+    pub fn rotate_left_one(&mut self) {
+        if self.len > 1 {
+            let node = self.pop_front_node().unwrap();
+            self.push_back_node(node);
+        }
+    }
+
+    /// Rotates the list `n` places to the left, so the element at index `n` becomes the front.
+    /// Walks to the cut from whichever end is closer, and rejoins the halves in O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate by {} in a list of length {}",
+            n,
+            self.len
+        );
+        if n == 0 || n == self.len {
+            return;
+        }
+        let (prev, node) = self.node_at(n);
+        unsafe { self.rotate_at(prev.unwrap(), node) };
+    }
+
+    /// Rotates the list `n` places to the right, so the element at index `len - n` becomes the
+    /// front. Walks to the cut from whichever end is closer, and rejoins the halves in O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate by {} in a list of length {}",
+            n,
+            self.len
+        );
+        self.rotate_left(self.len - n);
+    }
+
+    /// Starts caching the digest that `Hash` writes, making hashing O(1).
+    ///
+    /// `push_*`, `pop_*` and `append` keep the cache up to date, other mutations drop it. Call
+    /// this again to resume caching after such a mutation.
+    ///
+    /// Equal lists hash equally whether or not they cache, but with the `cached_hash` feature
+    /// hashing is no longer DoS resistant, see the crate documentation.
+    #[cfg(feature = "cached_hash")]
+    pub fn cache_hash(&mut self)
+    where
+        E: Hash,
+    {
+        self.hash_cache = Some(cached_hash::HashCache::new(self.iter()));
+    }
+
+    /// Returns whether the digest that `Hash` writes is currently cached.
+    #[cfg(feature = "cached_hash")]
+    pub fn is_hash_cached(&self) -> bool {
+        self.hash_cache.is_some()
+    }
+
+    /// Inserts the elements of `iter` at the front of the list, keeping their order.
+    ///
+    /// The elements are linked into a separate chain first, which is then attached in front of
+    /// the current head in O(1).
+    pub fn prepend_ordered<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        self.append_front(&mut iter.into_iter().collect());
+    }
+
+    /// Splits the list into `n` contiguous lists whose lengths differ by at most one, with the
+    /// longer lists first. If the list has fewer than `n` elements the trailing lists are empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn split_into(mut self, n: usize) -> Vec<Self> {
+        assert!(n != 0, "cannot split a list into zero parts");
+        let (base, extra) = (self.len / n, self.len % n);
+        (0..n)
+            .map(|i| self.split_front(base + (i < extra) as usize))
+            .collect()
+    }
+
+    /// Splits the list in two at the given index. Returns everything from `at` onwards, and
+    /// `self` keeps the elements before it. Walks from whichever end is closer to `at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        if at <= self.len / 2 {
+            let mut front = self.split_front(at);
+            self.swap_contents(&mut front);
+            front
+        } else {
+            self.split_back(self.len - at)
+        }
+    }
+
+    /// Splits off the last `count_from_end` elements into a new list, walking from the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count_from_end > len`.
+    pub fn split_off_back(&mut self, count_from_end: usize) -> Self {
+        assert!(
+            count_from_end <= self.len,
+            "Cannot split off more elements than the list contains"
+        );
+        self.split_back(count_from_end)
+    }
+
+    /// Detaches the first `n` elements as a new list, or all of them if the list is shorter.
+    /// The chain is cut once instead of popping and pushing every element.
+    pub fn pop_front_n(&mut self, n: usize) -> Self {
+        self.split_front(n.min(self.len))
+    }
+
+    /// Detaches the last `n` elements as a new list, or all of them if the list is shorter.
+    /// The chain is cut once instead of popping and pushing every element.
+    pub fn pop_back_n(&mut self, n: usize) -> Self {
+        self.split_back(n.min(self.len))
+    }
+
+    /// Consumes the list, moving the elements for which `pred` returns `true` into the first list
+    /// and the others into the second, both in their original order. The nodes are relinked,
+    /// nothing is allocated or cloned.
+    pub fn partition<F: FnMut(&E) -> bool>(mut self, mut pred: F) -> (Self, Self) {
+        let mut matching = Self::new();
+        let mut rest = Self::new();
+        while let Some(node) = self.pop_front_node() {
+            if pred(&node.element) {
+                matching.push_back_node(node);
+            } else {
+                rest.push_back_node(node);
+            }
+        }
+        (matching, rest)
+    }
+
+    /// Consumes the list and returns an iterator over the segments between elements for which
+    /// `pred` returns `true`, like `slice::split`. The matching elements are dropped, and the
+    /// segments are cut out of the list by relinking, walking it only once in total.
+    pub fn split<F: FnMut(&E) -> bool>(self, pred: F) -> Split<E, F> {
+        Split {
+            rest: Some(self),
+            pred,
+        }
+    }
+
+    /// Consumes both lists, combining their elements pairwise with `f` into a new list. Stops at
+    /// the end of the shorter list and drops the rest of the longer one.
+    pub fn zip_with<B, C, F>(mut self, mut other: LinkedList<B>, mut f: F) -> LinkedList<C>
+    where
+        F: FnMut(E, B) -> C,
+    {
+        let mut zipped = LinkedList::new();
+        while let (Some(a), Some(b)) = (self.pop_front(), other.pop_front()) {
+            zipped.push_back(f(a, b));
+        }
+        zipped
+    }
+
+    /// Consumes the list, collapsing each run of equal consecutive elements into its first element
+    /// and the length of the run.
+    pub fn run_length_encode(mut self) -> LinkedList<(E, usize)>
+    where
+        E: PartialEq,
+    {
+        let mut runs = LinkedList::new();
+        let mut run = None;
+        while let Some(elem) = self.pop_front() {
+            match &mut run {
+                Some((value, count)) if *value == elem => *count += 1,
+                _ => runs.extend(run.replace((elem, 1))),
+            }
+        }
+        runs.extend(run);
+        runs
+    }
+
+    /// Consumes the list and moves its elements into a `Vec`, allocated once with `len` capacity.
+    pub fn into_vec(self) -> Vec<E> {
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend(self);
+        vec
+    }
+
+    /// Clones the elements into a new `Vec`, allocated once with `len` capacity.
+    pub fn to_vec(&self) -> Vec<E>
+    where
+        E: Clone,
+    {
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend(self.iter().cloned());
+        vec
+    }
+
+    /// Consumes the list into an iterator over lists of `chunk_size` elements each, except for
+    /// the last one which may be shorter. The chunks are cut off the list, not copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn into_chunks(self, chunk_size: usize) -> IntoChunks<E> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        IntoChunks {
+            list: self,
+            chunk_size,
+        }
+    }
+
+    /// Consumes the list without freeing its nodes and returns its raw parts, e.g. for handing
+    /// ownership across an FFI boundary. The capacity limit, if any, is not preserved.
+    ///
+    /// The nodes are leaked unless the parts are passed back to `from_raw_parts`.
+    pub fn leak(self) -> RawList<E> {
+        let list = mem::ManuallyDrop::new(self);
+        let as_raw = |node: Option<NonNull<Node<E>>>| {
+            node.map_or(ptr::null_mut(), |node| node.as_ptr().cast())
+        };
+        RawList {
+            head: as_raw(list.head),
+            tail: as_raw(list.tail),
+            len: list.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Reconstitutes a list from the raw parts returned by `leak`.
+    ///
+    /// # Safety
+    ///
+    /// `head`, `tail` and `len` must be the fields of a `RawList<E>` returned by `leak` for this
+    /// same `E`, and they must not be used to reconstitute a list more than once. Passing
+    /// anything else, e.g. a mismatched `len`, is undefined behaviour; debug builds walk the
+    /// list to catch the latter.
+    pub unsafe fn from_raw_parts(head: *mut c_void, tail: *mut c_void, len: usize) -> Self {
+        let list = LinkedList {
+            head: NonNull::new(head.cast()),
+            tail: NonNull::new(tail.cast()),
+            len,
+            ..Self::new()
+        };
+        if cfg!(debug_assertions) {
+            let mut walked = 0;
+            let mut prev = None;
+            let mut cur = list.head;
+            while let Some(node) = cur {
+                walked += 1;
+                cur = (*node.as_ptr()).xor(prev);
+                prev = Some(node);
+            }
+            debug_assert!(
+                prev == list.tail,
+                "`tail` is not the end of the list at `head`"
+            );
+            debug_assert_eq!(walked, len, "`len` does not match the number of nodes");
+        }
+        list
+    }
+
+    /// Moves all elements of `other` into the list, alternating between the two lists starting
+    /// with `self`. Once one of them runs out, the rest of the other one follows. The nodes are
+    /// relinked in a single pass, leaving `other` empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements of `other` would exceed the capacity limit.
+    pub fn interleave(&mut self, other: &mut Self) {
+        self.check_limit(other.len);
+        let mut mine = self.take_all();
+        while !mine.is_empty() && !other.is_empty() {
+            self.push_back_node(mine.pop_front_node().unwrap());
+            self.push_back_node(other.pop_front_node().unwrap());
+        }
+        self.append(&mut mine);
+        self.append(other);
+    }
+
+    /// Joins `lists` front to back into a single list by linking them together in O(1) each.
+    pub fn concat<I: IntoIterator<Item = LinkedList<E>>>(lists: I) -> Self {
+        let mut list = Self::new();
+        for mut other in lists {
+            list.append(&mut other);
+        }
+        list
+    }
+
+    pub fn iter(&self) -> Iter<'_, E> {
+        Iter {
+            head: self.head,
+            prev_head: None,
+            tail: self.tail,
+            prev_tail: None,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, E> {
+        self.invalidate_hash();
+        IterMut {
+            head: self.head,
+            prev_head: None,
+            tail: self.tail,
+            prev_tail: None,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the elements in `range`. Both ends are found by walking from
+    /// whichever end of the list is closer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the end of the list.
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> Iter<'_, E> {
+        let (start, end) = self.range_indices(range);
+        let [head, prev_head, tail, prev_tail] = self.range_links(start, end);
+        Iter {
+            head,
+            prev_head,
+            tail,
+            prev_tail,
+            len: end - start,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the elements in `range` that allows modifying them. Both ends are
+    /// found by walking from whichever end of the list is closer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the end of the list.
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> IterMut<'_, E> {
+        let (start, end) = self.range_indices(range);
+        self.invalidate_hash();
+        let [head, prev_head, tail, prev_tail] = self.range_links(start, end);
+        IterMut {
+            head,
+            prev_head,
+            tail,
+            prev_tail,
+            len: end - start,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator from back to front. Unlike `iter().rev()` this is a plain `Iter`, and
+    /// `next_back` walks towards the back again.
+    pub fn iter_rev(&self) -> Iter<'_, E> {
+        // The links don't encode a direction, so swapping the ends reverses the walk.
+        Iter {
+            head: self.tail,
+            tail: self.head,
+            ..self.iter()
+        }
+    }
+
+    /// Returns an iterator over the elements and their indices. Unlike `iter().enumerate()`,
+    /// `next_back` yields the indices counting down from `len - 1`.
+    pub fn iter_indexed(&self) -> IterIndexed<'_, E> {
+        IterIndexed {
+            iter: self.iter(),
+            front: 0,
+        }
+    }
+
+    /// Returns an iterator over each pair of adjacent elements, from front to back.
+    pub fn pairs(&self) -> Pairs<'_, E> {
+        let mut iter = self.iter();
+        let prev = iter.next();
+        Pairs { iter, prev }
+    }
+
+    /// Returns a cursor at the front element, or at the ghost position if the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, E> {
+        Cursor::front(self)
+    }
+
+    /// Returns a cursor at the back element, or at the ghost position if the list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, E> {
+        Cursor::back(self)
+    }
+
+    /// Returns a cursor at the element at `index`, walking from whichever end is closer. An
+    /// `index` of `len` gives the ghost position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn cursor_at(&self, index: usize) -> Cursor<'_, E> {
+        self.check_cursor_index(index);
+        Cursor::at(self, index)
+    }
+
+    /// Returns a cursor that can edit the list, at the front element or at the ghost position if
+    /// the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, E> {
+        CursorMut::front(self)
+    }
+
+    /// Returns a cursor that can edit the list, at the back element or at the ghost position if
+    /// the list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, E> {
+        CursorMut::back(self)
+    }
+
+    /// Returns a cursor that can edit the list, at the element at `index`, walking from
+    /// whichever end is closer. An `index` of `len` gives the ghost position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn cursor_at_mut(&mut self, index: usize) -> CursorMut<'_, E> {
+        self.check_cursor_index(index);
+        CursorMut::at(self, index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Default for LinkedList<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> FromIterator<E> for LinkedList<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, const N: usize> From<[E; N]> for LinkedList<E> {
+    fn from(arr: [E; N]) -> Self {
+        IntoIterator::into_iter(arr).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> From<Vec<E>> for LinkedList<E> {
+    fn from(vec: Vec<E>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> From<Box<[E]>> for LinkedList<E> {
+    fn from(slice: Box<[E]>) -> Self {
+        Vec::from(slice).into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> From<LinkedList<E>> for Vec<E> {
+    fn from(list: LinkedList<E>) -> Self {
+        list.into_vec()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> FromIterator<LinkedList<E>> for LinkedList<E> {
+    /// Joins the lists front to back, linking each one in O(1), see `LinkedList::concat`.
+    fn from_iter<I: IntoIterator<Item = LinkedList<E>>>(iter: I) -> Self {
+        Self::concat(iter)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Extend<E> for LinkedList<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        iter.into_iter().for_each(move |elem| self.push_back(elem));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E: 'a + Copy> Extend<&'a E> for LinkedList<E> {
+    fn extend<I: IntoIterator<Item = &'a E>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Index<usize> for LinkedList<E> {
+    type Output = E;
+
+    fn index(&self, index: usize) -> &E {
+        let len = self.len;
+        match self.get(index) {
+            Some(elem) => elem,
+            None => panic!("index {} out of bounds for a list of length {}", index, len),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> IndexMut<usize> for LinkedList<E> {
+    fn index_mut(&mut self, index: usize) -> &mut E {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(elem) => elem,
+            None => panic!("index {} out of bounds for a list of length {}", index, len),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: PartialEq> PartialEq for LinkedList<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Eq> Eq for LinkedList<E> {}
+
+#[cfg(feature = "alloc")]
+impl<A: PartialEq<B>, B> PartialEq<[B]> for LinkedList<A> {
+    fn eq(&self, other: &[B]) -> bool {
+        self.len == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: PartialEq<B>, B> PartialEq<&[B]> for LinkedList<A> {
+    fn eq(&self, other: &&[B]) -> bool {
+        *self == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: PartialEq<B>, B, const N: usize> PartialEq<[B; N]> for LinkedList<A> {
+    fn eq(&self, other: &[B; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: PartialEq<B>, B> PartialEq<Vec<B>> for LinkedList<A> {
+    fn eq(&self, other: &Vec<B>) -> bool {
+        *self == other[..]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: PartialOrd> PartialOrd for LinkedList<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Ord> Ord for LinkedList<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+/// Both `clone` and `clone_from` give the clone the capacity limit of the source list.
+#[cfg(feature = "alloc")]
+impl<E: Clone> Clone for LinkedList<E> {
+    fn clone(&self) -> Self {
+        let mut list = LinkedList {
+            limit: self.limit,
+            ..Self::new()
+        };
+        list.extend(self.iter().cloned());
+        list
+    }
+
+    /// Overwrites the elements of the existing nodes with `clone_from`, so nodes are only freed
+    /// or allocated for the difference in length.
+    fn clone_from(&mut self, source: &Self) {
+        self.limit = source.limit;
+        self.truncate(source.len);
+        let mut source_iter = source.iter();
+        for (elem, source_elem) in self.iter_mut().zip(&mut source_iter) {
+            elem.clone_from(source_elem);
+        }
+        self.extend(source_iter.cloned());
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "arbitrary"))]
+impl<'a, E: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for LinkedList<E> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        alloc::vec::Vec::<E>::arbitrary(u).map(FromIterator::from_iter)
+    }
+
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        alloc::vec::Vec::<E>::arbitrary_take_rest(u).map(FromIterator::from_iter)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> From<std::collections::LinkedList<E>> for LinkedList<E> {
+    fn from(list: std::collections::LinkedList<E>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> From<LinkedList<E>> for std::collections::LinkedList<E> {
+    fn from(list: LinkedList<E>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> From<std::collections::VecDeque<E>> for LinkedList<E> {
+    fn from(deque: std::collections::VecDeque<E>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> From<LinkedList<E>> for std::collections::VecDeque<E> {
+    fn from(list: LinkedList<E>) -> Self {
+        let mut deque = std::collections::VecDeque::with_capacity(list.len);
+        deque.extend(list);
+        deque
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: fmt::Debug> fmt::Debug for LinkedList<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "cached_hash")))]
+impl<E: Hash> Hash for LinkedList<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for elt in self {
+            elt.hash(state);
+        }
+    }
+}
+
+/// With the `cached_hash` feature a list hashes its length and a digest of its elements. The
+/// digest is only recomputed if the list isn't caching it, see `LinkedList::cache_hash`. This
+/// applies to every list, and the digest doesn't depend on the `Hasher`'s key.
+#[cfg(feature = "cached_hash")]
+impl<E: Hash> Hash for LinkedList<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        match &self.hash_cache {
+            Some(cache) => cache.digest.hash(state),
+            None => cached_hash::digest(self.iter()).hash(state),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Drop for LinkedList<E> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<E: Send> Send for LinkedList<E> {}
+#[cfg(feature = "alloc")]
+unsafe impl<E: Sync> Sync for LinkedList<E> {}
+
+unsafe impl<E: Send> Send for Iter<'_, E> {}
+unsafe impl<E: Sync> Sync for Iter<'_, E> {}
+
+#[cfg(feature = "alloc")]
+unsafe impl<E: Send> Send for IterMut<'_, E> {}
+#[cfg(feature = "alloc")]
+unsafe impl<E: Sync> Sync for IterMut<'_, E> {}
+
+/// Merges two sorted lists onto the back of `out` by moving their nodes. Dropping it, also when
+/// the comparison panics, appends whatever wasn't merged yet, so no node is lost.
+#[cfg(feature = "alloc")]
+struct Merge<'a, E> {
+    out: &'a mut LinkedList<E>,
+    left: LinkedList<E>,
+    right: LinkedList<E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Merge<'_, E> {
+    fn run<F: FnMut(&E, &E) -> Ordering>(mut self, compare: &mut F) {
+        while let (Some(left), Some(right)) = (self.left.head, self.right.head) {
+            // Only take from the right on a strict `Greater` to keep the sort stable.
+            let ordering =
+                unsafe { compare(&(*left.as_ptr()).element, &(*right.as_ptr()).element) };
+            let node = match ordering {
+                Ordering::Greater => self.right.pop_front_node(),
+                _ => self.left.pop_front_node(),
+            };
+            self.out.push_back_node(node.unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Drop for Merge<'_, E> {
+    fn drop(&mut self) {
+        self.out.append(&mut self.left);
+        self.out.append(&mut self.right);
+    }
+}
+
+/// The raw parts of a leaked `LinkedList`, see `LinkedList::leak`.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct RawList<E> {
+    pub head: *mut c_void,
+    pub tail: *mut c_void,
+    pub len: usize,
+    marker: PhantomData<Box<Node<E>>>,
+}
+
+/// A possibly null pointer to a neighbouring node.
+type Link<E> = Option<NonNull<Node<E>>>;
+
+/// A list node. `LinkedList` allocates its nodes itself, `IntrusiveList` links nodes owned by the
+/// caller.
+#[derive(Debug)]
+pub struct Node<E> {
+    prev_x_next: usize,
+    element: E,
+}
+
+impl<E> Node<E> {
+    pub const fn new(element: E) -> Self {
+        Node {
+            prev_x_next: 0,
+            element,
+        }
+    }
+
+    pub fn element(&self) -> &E {
+        &self.element
+    }
+
+    pub fn element_mut(&mut self) -> &mut E {
+        &mut self.element
+    }
+
+    fn xor(&self, other: Option<NonNull<Self>>) -> Option<NonNull<Self>> {
+        let other = other.map(|nn| nn.as_ptr() as usize).unwrap_or(0);
+        let result = other ^ self.prev_x_next;
+        NonNull::new(result as *mut _)
+    }
+
+    fn xor_assign(&mut self, other: Option<NonNull<Self>>) {
+        let other = other.map(|nn| nn.as_ptr() as usize).unwrap_or(0);
+        self.prev_x_next ^= other;
+    }
+
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::boxed_local)]
+    fn into_element(self: Box<Self>) -> E {
+        self.element
+    }
+}
+
+pub struct Iter<'a, E: 'a> {
+    head: Option<NonNull<Node<E>>>,
+    prev_head: Option<NonNull<Node<E>>>,
+    tail: Option<NonNull<Node<E>>>,
+    prev_tail: Option<NonNull<Node<E>>>,
+    len: usize,
+    marker: PhantomData<&'a Node<E>>,
+}
+
+/// Moves an iterator end `n` nodes along without touching the elements. The caller makes sure
+/// there are that many nodes left.
+fn advance<E>(node: &mut Link<E>, prev: &mut Link<E>, n: usize) {
+    for _ in 0..n {
+        let cur = node.unwrap();
+        *node = unsafe { (*cur.as_ptr()).xor(*prev) };
+        *prev = Some(cur);
+    }
+}
+
+/// Formats the elements an iterator has yet to yield. Only ever reads through the nodes: other
+/// iterators over the same list may be alive.
+struct Remaining<'a, E>(Iter<'a, E>);
+
+impl<E: fmt::Debug> fmt::Debug for Remaining<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for Iter<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&Remaining(self.clone()))
+            .field(&self.len)
+            .finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<'a, E> Iterator for Iter<'a, E> {
+    type Item = &'a E;
+
+    fn next(&mut self) -> Option<&'a E> {
+        if self.len == 0 {
+            None
+        } else {
+            self.head.map(|node| unsafe {
+                let node = &*node.as_ptr();
+                self.len -= 1;
+                self.head = node.xor(self.prev_head);
+                self.prev_head = Some(node.into());
+                &node.element
+            })
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn last(mut self) -> Option<&'a E> {
+        self.next_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<&'a E> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        advance(&mut self.head, &mut self.prev_head, n);
+        self.len -= n;
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        self.len
+    }
+
+    fn fold<B, F: FnMut(B, &'a E) -> B>(self, init: B, mut f: F) -> B {
+        let (mut node, mut prev) = (self.head, self.prev_head);
+        let mut acc = init;
+        for _ in 0..self.len {
+            unsafe {
+                let cur = &*node.unwrap().as_ptr();
+                acc = f(acc, &cur.element);
+                node = cur.xor(prev);
+                prev = Some(cur.into());
+            }
+        }
+        acc
+    }
+}
+
+impl<'a, E> DoubleEndedIterator for Iter<'a, E> {
     fn next_back(&mut self) -> Option<&'a E> {
         if self.len == 0 {
             None
@@ -339,8 +2338,37 @@ impl<'a, E> DoubleEndedIterator for Iter<'a, E> {
             })
         }
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<&'a E> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        advance(&mut self.tail, &mut self.prev_tail, n);
+        self.len -= n;
+        self.next_back()
+    }
+
+    fn rfold<B, F: FnMut(B, &'a E) -> B>(self, init: B, mut f: F) -> B {
+        let (mut node, mut next) = (self.tail, self.prev_tail);
+        let mut acc = init;
+        for _ in 0..self.len {
+            unsafe {
+                let cur = &*node.unwrap().as_ptr();
+                acc = f(acc, &cur.element);
+                node = cur.xor(next);
+                next = Some(cur.into());
+            }
+        }
+        acc
+    }
 }
 
+impl<E> ExactSizeIterator for Iter<'_, E> {}
+
+impl<E> FusedIterator for Iter<'_, E> {}
+
+#[cfg(feature = "alloc")]
 impl<'a, E> IntoIterator for &'a LinkedList<E> {
     type Item = &'a E;
     type IntoIter = Iter<'a, E>;
@@ -349,3 +2377,449 @@ impl<'a, E> IntoIterator for &'a LinkedList<E> {
         self.iter()
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<E> IntoIterator for LinkedList<E> {
+    type Item = E;
+    type IntoIter = IntoIter<E>;
+
+    fn into_iter(self) -> IntoIter<E> {
+        IntoIter { list: self }
+    }
+}
+
+/// An owning iterator over the elements of a list. Dropping it drops the remaining elements.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct IntoIter<E> {
+    list: LinkedList<E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Iterator for IntoIter<E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> DoubleEndedIterator for IntoIter<E> {
+    fn next_back(&mut self) -> Option<E> {
+        self.list.pop_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> ExactSizeIterator for IntoIter<E> {}
+
+#[cfg(feature = "alloc")]
+impl<E> FusedIterator for IntoIter<E> {}
+
+#[cfg(feature = "alloc")]
+pub struct IterMut<'a, E: 'a> {
+    head: Option<NonNull<Node<E>>>,
+    prev_head: Option<NonNull<Node<E>>>,
+    tail: Option<NonNull<Node<E>>>,
+    prev_tail: Option<NonNull<Node<E>>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<E>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: fmt::Debug> fmt::Debug for IterMut<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let remaining = Iter {
+            head: self.head,
+            prev_head: self.prev_head,
+            tail: self.tail,
+            prev_tail: self.prev_tail,
+            len: self.len,
+            marker: PhantomData,
+        };
+        f.debug_tuple("IterMut")
+            .field(&Remaining(remaining))
+            .field(&self.len)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> Iterator for IterMut<'a, E> {
+    type Item = &'a mut E;
+
+    fn next(&mut self) -> Option<&'a mut E> {
+        if self.len == 0 {
+            None
+        } else {
+            self.head.map(|node| unsafe {
+                let node = &mut *node.as_ptr();
+                self.len -= 1;
+                self.head = node.xor(self.prev_head);
+                self.prev_head = Some(node.into());
+                &mut node.element
+            })
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn last(mut self) -> Option<&'a mut E> {
+        self.next_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<&'a mut E> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        advance(&mut self.head, &mut self.prev_head, n);
+        self.len -= n;
+        self.next()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> DoubleEndedIterator for IterMut<'a, E> {
+    fn next_back(&mut self) -> Option<&'a mut E> {
+        if self.len == 0 {
+            None
+        } else {
+            self.tail.map(|node| unsafe {
+                let node = &mut *node.as_ptr();
+                self.len -= 1;
+                self.tail = node.xor(self.prev_tail);
+                self.prev_tail = Some(node.into());
+                &mut node.element
+            })
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<&'a mut E> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        advance(&mut self.tail, &mut self.prev_tail, n);
+        self.len -= n;
+        self.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> ExactSizeIterator for IterMut<'_, E> {}
+
+#[cfg(feature = "alloc")]
+impl<E> FusedIterator for IterMut<'_, E> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> IntoIterator for &'a mut LinkedList<E> {
+    type Item = &'a mut E;
+    type IntoIter = IterMut<'a, E>;
+
+    fn into_iter(self) -> IterMut<'a, E> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over pairs of adjacent elements, see `LinkedList::pairs`.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct Pairs<'a, E: 'a> {
+    iter: Iter<'a, E>,
+    prev: Option<&'a E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Clone for Pairs<'_, E> {
+    fn clone(&self) -> Self {
+        Pairs {
+            iter: self.iter.clone(),
+            prev: self.prev,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> Iterator for Pairs<'a, E> {
+    type Item = (&'a E, &'a E);
+
+    fn next(&mut self) -> Option<(&'a E, &'a E)> {
+        let next = self.iter.next()?;
+        self.prev.replace(next).map(|prev| (prev, next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> ExactSizeIterator for Pairs<'_, E> {}
+
+#[cfg(feature = "alloc")]
+impl<E> FusedIterator for Pairs<'_, E> {}
+
+/// An iterator over owned chunks of a list, see `LinkedList::into_chunks`.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct IntoChunks<E> {
+    list: LinkedList<E>,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Iterator for IntoChunks<E> {
+    type Item = LinkedList<E>;
+
+    fn next(&mut self) -> Option<LinkedList<E>> {
+        if self.list.len == 0 {
+            None
+        } else {
+            let at = self.chunk_size.min(self.list.len);
+            Some(self.list.split_front(at))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.list.len.div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> ExactSizeIterator for IntoChunks<E> {}
+
+#[cfg(feature = "alloc")]
+impl<E> FusedIterator for IntoChunks<E> {}
+
+/// An iterator removing matching elements from a list, see `LinkedList::extract_if`.
+#[cfg(feature = "alloc")]
+pub struct ExtractIf<'a, E: 'a, F> {
+    list: &'a mut LinkedList<E>,
+    prev: Link<E>,
+    cur: Link<E>,
+    remaining: usize,
+    filter: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: fmt::Debug, F> fmt::Debug for ExtractIf<'_, E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtractIf").field(&self.list).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, F: FnMut(&mut E) -> bool> Iterator for ExtractIf<'_, E, F> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        while let Some(node) = self.cur {
+            unsafe {
+                self.cur = (*node.as_ptr()).xor(self.prev);
+                self.remaining -= 1;
+                if (self.filter)(&mut (*node.as_ptr()).element) {
+                    return Some(self.list.unlink_node(self.prev, node).into_element());
+                }
+            }
+            self.prev = Some(node);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, F: FnMut(&mut E) -> bool> FusedIterator for ExtractIf<'_, E, F> {}
+
+/// An iterator over elements removed from a list, see `LinkedList::drain`.
+#[cfg(feature = "alloc")]
+pub struct Drain<'a, E: 'a> {
+    iter: IntoIter<E>,
+    marker: PhantomData<&'a mut LinkedList<E>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: fmt::Debug> fmt::Debug for Drain<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.iter.list).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Iterator for Drain<'_, E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> DoubleEndedIterator for Drain<'_, E> {
+    fn next_back(&mut self) -> Option<E> {
+        self.iter.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> ExactSizeIterator for Drain<'_, E> {}
+
+#[cfg(feature = "alloc")]
+impl<E> FusedIterator for Drain<'_, E> {}
+
+/// An iterator over the segments of a list, see `LinkedList::split`.
+#[cfg(feature = "alloc")]
+pub struct Split<E, F> {
+    // `None` once the last segment has been returned.
+    rest: Option<LinkedList<E>>,
+    pred: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: fmt::Debug, F> fmt::Debug for Split<E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Split").field(&self.rest).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, F: FnMut(&E) -> bool> Iterator for Split<E, F> {
+    type Item = LinkedList<E>;
+
+    fn next(&mut self) -> Option<LinkedList<E>> {
+        let rest = self.rest.as_mut()?;
+        let mut prev = None;
+        let mut cur = rest.head;
+        let mut len = 0;
+        while let Some(node) = cur {
+            unsafe {
+                if (self.pred)(&(*node.as_ptr()).element) {
+                    let segment = match prev {
+                        Some(prev) => rest.split_between(prev, node, len),
+                        None => LinkedList::new(),
+                    };
+                    drop(rest.pop_front_node());
+                    return Some(segment);
+                }
+                cur = (*node.as_ptr()).xor(prev);
+            }
+            prev = Some(node);
+            len += 1;
+        }
+        self.rest.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.rest {
+            Some(rest) => (1, Some(rest.len + 1)),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, F: FnMut(&E) -> bool> FusedIterator for Split<E, F> {}
+
+/// An iterator removing a prefix of a list, see `LinkedList::drain_while`.
+#[cfg(feature = "alloc")]
+pub struct DrainWhile<'a, E: 'a, F> {
+    list: &'a mut LinkedList<E>,
+    pred: F,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: fmt::Debug, F> fmt::Debug for DrainWhile<'_, E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DrainWhile").field(&self.list).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, F: FnMut(&E) -> bool> Iterator for DrainWhile<'_, E, F> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        if self.done {
+            return None;
+        }
+        let pred = &mut self.pred;
+        let next = self.list.pop_front_if(|elem| pred(elem));
+        self.done = next.is_none();
+        next
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.list.len))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E, F: FnMut(&E) -> bool> FusedIterator for DrainWhile<'_, E, F> {}
+
+/// An iterator over elements and their indices, see `LinkedList::iter_indexed`.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct IterIndexed<'a, E: 'a> {
+    iter: Iter<'a, E>,
+    front: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Clone for IterIndexed<'_, E> {
+    fn clone(&self) -> Self {
+        IterIndexed {
+            iter: self.iter.clone(),
+            front: self.front,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> Iterator for IterIndexed<'a, E> {
+    type Item = (usize, &'a E);
+
+    fn next(&mut self) -> Option<(usize, &'a E)> {
+        let elem = self.iter.next()?;
+        self.front += 1;
+        Some((self.front - 1, elem))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> DoubleEndedIterator for IterIndexed<'a, E> {
+    fn next_back(&mut self) -> Option<(usize, &'a E)> {
+        let elem = self.iter.next_back()?;
+        Some((self.front + self.iter.len, elem))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E> ExactSizeIterator for IterIndexed<'_, E> {}
+
+#[cfg(feature = "alloc")]
+impl<E> FusedIterator for IterIndexed<'_, E> {}
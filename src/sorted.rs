@@ -0,0 +1,141 @@
+//! A list that keeps its elements sorted.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FromIterator;
+
+use crate::{IntoIter, Iter, LinkedList};
+
+/// A `LinkedList` whose elements are always in ascending order. Equal elements stay in the order
+/// they were inserted.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SortedList<E: Ord> {
+    list: LinkedList<E>,
+}
+
+impl<E: Ord> SortedList<E> {
+    pub fn new() -> Self {
+        SortedList {
+            list: LinkedList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Inserts `elem` after all elements less than or equal to it.
+    pub fn insert(&mut self, elem: E) {
+        self.list.insert_sorted(elem);
+    }
+
+    /// Merges all elements of `other` into this list in a single pass.
+    pub fn merge(&mut self, other: Self) {
+        self.list.merge(other.list);
+    }
+
+    /// Returns `true` if the list contains an element equal to `x`. Stops at the first element
+    /// greater than `x`.
+    pub fn contains(&self, x: &E) -> bool {
+        for elem in &self.list {
+            match elem.cmp(x) {
+                Ordering::Less => {}
+                Ordering::Equal => return true,
+                Ordering::Greater => return false,
+            }
+        }
+        false
+    }
+
+    /// Returns the smallest element.
+    pub fn min(&self) -> Option<&E> {
+        self.list.front()
+    }
+
+    /// Returns the largest element.
+    pub fn max(&self) -> Option<&E> {
+        self.list.back()
+    }
+
+    /// Removes and returns the smallest element.
+    pub fn pop_min(&mut self) -> Option<E> {
+        self.list.pop_front()
+    }
+
+    /// Removes and returns the largest element.
+    pub fn pop_max(&mut self) -> Option<E> {
+        self.list.pop_back()
+    }
+
+    pub fn iter(&self) -> Iter<'_, E> {
+        self.list.iter()
+    }
+
+    /// Returns the underlying list.
+    pub fn as_list(&self) -> &LinkedList<E> {
+        &self.list
+    }
+
+    /// Returns the underlying list, which is no longer kept sorted.
+    pub fn into_list(self) -> LinkedList<E> {
+        self.list
+    }
+}
+
+impl<E: Ord> Default for SortedList<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Ord> From<LinkedList<E>> for SortedList<E> {
+    /// Sorts `list` and wraps it.
+    fn from(mut list: LinkedList<E>) -> Self {
+        list.sort();
+        SortedList { list }
+    }
+}
+
+impl<E: Ord> FromIterator<E> for SortedList<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<LinkedList<E>>())
+    }
+}
+
+impl<E: Ord> Extend<E> for SortedList<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        self.merge(iter.into_iter().collect());
+    }
+}
+
+impl<E: Ord + fmt::Debug> fmt::Debug for SortedList<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<'a, E: Ord> IntoIterator for &'a SortedList<E> {
+    type Item = &'a E;
+    type IntoIter = Iter<'a, E>;
+
+    fn into_iter(self) -> Iter<'a, E> {
+        self.iter()
+    }
+}
+
+impl<E: Ord> IntoIterator for SortedList<E> {
+    type Item = E;
+    type IntoIter = IntoIter<E>;
+
+    fn into_iter(self) -> IntoIter<E> {
+        self.list.into_iter()
+    }
+}
@@ -1,10 +1,5 @@
 use super::*;
 
-use std::thread;
-use std::vec::Vec;
-
-use rand::{thread_rng, RngCore};
-
 fn list_from<T: Clone>(v: &[T]) -> LinkedList<T> {
     v.iter().cloned().collect()
 }
@@ -101,3 +96,2318 @@ fn test_append() {
     assert_eq!(n.pop_front(), Some(3));
     check_links(&n);
 }
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use rand::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::seed_from_u64(0);
+    let mut data = [0u8; 256];
+    for _ in 0..100 {
+        rng.fill_bytes(&mut data);
+        let list = LinkedList::<u32>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let vec = Vec::<u32>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        check_links(&list);
+        assert!(list.iter().eq(&vec));
+        assert!(list.iter().rev().eq(vec.iter().rev()));
+
+        let list = LinkedList::<u32>::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+        check_links(&list);
+    }
+}
+
+#[test]
+fn test_capacity_limit() {
+    let mut m = LinkedList::with_capacity_limit(3);
+    assert_eq!(m.capacity_limit(), Some(3));
+    m.extend(vec![1, 2]);
+    m.push_front(0);
+    assert_eq!(m.try_push_back(3), Err(3));
+    assert_eq!(m.len(), 3);
+    check_links(&m);
+
+    assert_eq!(m.push_back_evicting(3), Some(0));
+    assert_eq!(m.push_back_evicting(4), Some(1));
+    assert_eq!(m.len(), 3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 4]));
+
+    m.pop_front();
+    assert_eq!(m.try_push_back(5), Ok(()));
+    assert_eq!(m.try_push_back(6), Err(6));
+    assert_eq!(m, list_from(&[3, 4, 5]));
+
+    // The limit belongs to the list, not its contents.
+    let mut n = LinkedList::with_capacity_limit(2);
+    let mut o = list_from(&[1, 2]);
+    n.append(&mut o);
+    assert_eq!(n.capacity_limit(), Some(2));
+    assert_eq!(o.capacity_limit(), None);
+    check_links(&n);
+    assert_eq!(n.clone().capacity_limit(), Some(2));
+    assert_eq!(n.clone_reversed().capacity_limit(), Some(2));
+    let mut p = LinkedList::with_capacity_limit(5);
+    p.clone_from(&n);
+    assert_eq!(p.capacity_limit(), Some(2));
+    p.clone_from(&o);
+    assert_eq!(p.capacity_limit(), None);
+    o.clone_from(&n);
+    assert_eq!(o.capacity_limit(), Some(2));
+
+    let mut z = LinkedList::with_capacity_limit(0);
+    assert_eq!(z.push_back_evicting(1), Some(1));
+    assert_eq!(z.len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "capacity limit of 2 exceeded")]
+fn test_capacity_limit_extend() {
+    let mut m = LinkedList::with_capacity_limit(2);
+    m.extend(vec![1, 2, 3]);
+}
+
+#[test]
+fn test_leak_from_raw_parts() {
+    let m = list_from(&[String::from("a"), String::from("b"), String::from("c")]);
+    let raw = m.leak();
+    assert_eq!(raw.len, 3);
+    let m = unsafe { LinkedList::<String>::from_raw_parts(raw.head, raw.tail, raw.len) };
+    check_links(&m);
+    assert_eq!(m, list_from(&["a".into(), "b".into(), "c".into()]));
+
+    let raw = LinkedList::<String>::new().leak();
+    assert!(raw.head.is_null() && raw.tail.is_null());
+    let m = unsafe { LinkedList::<String>::from_raw_parts(raw.head, raw.tail, raw.len) };
+    check_links(&m);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_split_into() {
+    let v: Vec<i32> = (0..10).collect();
+    let parts = list_from(&v).split_into(3);
+    let lens: Vec<usize> = parts.iter().map(LinkedList::len).collect();
+    assert_eq!(lens, [4, 3, 3]);
+    for part in &parts {
+        check_links(part);
+    }
+    assert!(parts.iter().flatten().eq(&v));
+
+    let parts = list_from(&[1, 2]).split_into(4);
+    let lens: Vec<usize> = parts.iter().map(LinkedList::len).collect();
+    assert_eq!(lens, [1, 1, 0, 0]);
+    for part in &parts {
+        check_links(part);
+    }
+
+    let parts = list_from(&v).split_into(1);
+    assert_eq!(parts, [list_from(&v)]);
+    check_links(&parts[0]);
+}
+
+#[test]
+#[should_panic]
+fn test_split_into_zero() {
+    list_from(&[1, 2, 3]).split_into(0);
+}
+
+#[cfg(feature = "cached_hash")]
+#[test]
+fn test_cached_hash() {
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash<T: Hash>(t: &T) -> u64 {
+        let mut s = DefaultHasher::new();
+        t.hash(&mut s);
+        s.finish()
+    }
+
+    fn check_cache(list: &LinkedList<i32>) {
+        let cache = list.hash_cache.expect("hash cache");
+        assert_eq!(cache.digest, cached_hash::digest(list.iter()));
+    }
+
+    let mut m = list_from(&[1, 2, 3]);
+    m.cache_hash();
+    assert!(m.is_hash_cached());
+    check_cache(&m);
+
+    m.push_back(4);
+    m.push_front(0);
+    check_cache(&m);
+    assert_eq!(m.pop_back(), Some(4));
+    assert_eq!(m.pop_front(), Some(0));
+    assert_eq!(m.pop_front(), Some(1));
+    check_cache(&m);
+
+    let mut n = list_from(&[7, 8]);
+    m.append(&mut n);
+    check_cache(&m);
+    let mut n = list_from(&[9]);
+    n.cache_hash();
+    m.append(&mut n);
+    check_cache(&m);
+    check_cache(&n);
+    n.push_back(1);
+    check_cache(&n);
+    let mut o = list_from(&[3, 4]);
+    m.append_front(&mut o);
+    check_cache(&m);
+
+    while m.pop_back().is_some() {
+        check_cache(&m);
+    }
+    m.extend(vec![5, 6, 7]);
+    check_cache(&m);
+
+    for at in 0..=3 {
+        let mut p = list_from(&[1, 2, 3]);
+        p.cache_hash();
+        let q = p.split_off(at);
+        for list in [&p, &q] {
+            if list.is_hash_cached() {
+                check_cache(list);
+            }
+        }
+    }
+
+    let mut p = list_from(&[1, 4, 6]);
+    p.cache_hash();
+    p.merge(list_from(&[2, 3, 5, 7]));
+    check_cache(&p);
+
+    // Equal lists hash equally whether or not they cache.
+    let uncached = list_from(&[5, 6, 7]);
+    assert!(!uncached.is_hash_cached());
+    assert_eq!(hash(&m), hash(&uncached));
+    assert_ne!(hash(&m), hash(&list_from(&[7, 6, 5])));
+}
+
+#[test]
+fn test_retain_count() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    assert_eq!(m.retain_count(|&x| x == 2 || x == 4), 3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 4]));
+
+    assert_eq!(m.retain_count(|_| true), 0);
+    assert_eq!(m.retain_count(|_| false), 2);
+    check_links(&m);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_pairs() {
+    let m = list_from(&[1, 2, 3, 4]);
+    let pairs: Vec<_> = m.pairs().map(|(&a, &b)| (a, b)).collect();
+    assert_eq!(pairs, [(1, 2), (2, 3), (3, 4)]);
+    assert_eq!(m.pairs().size_hint(), (3, Some(3)));
+
+    assert_eq!(list_from(&[1]).pairs().next(), None);
+    assert_eq!(LinkedList::<i32>::new().pairs().next(), None);
+}
+
+#[test]
+fn test_first_last() {
+    let mut m = LinkedList::new();
+    assert_eq!(m.first(), None);
+    assert_eq!(m.last(), None);
+    m.push_back(1);
+    assert_eq!(m.first(), Some(&1));
+    assert_eq!(m.last(), Some(&1));
+    m.push_back(2);
+    m.push_front(0);
+    assert_eq!(m.first(), Some(&0));
+    assert_eq!(m.last(), Some(&2));
+}
+
+#[test]
+fn test_memory_usage() {
+    let mut m = LinkedList::new();
+    assert_eq!(m.memory_usage(), 0);
+    for len in 1..=3 {
+        m.push_back(0u64);
+        assert_eq!(m.memory_usage(), len * mem::size_of::<Node<u64>>());
+    }
+    let m = list_from(&[(); 4]);
+    assert_eq!(m.memory_usage(), 4 * mem::size_of::<usize>());
+}
+
+#[test]
+fn test_rotate_to() {
+    let mut m = list_from(&[1, 2, 3, 4]);
+    assert!(m.rotate_to(|&x| x == 3));
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 4, 1, 2]));
+
+    assert!(m.rotate_to(|&x| x == 2));
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 4, 1]));
+
+    assert!(m.rotate_to(|&x| x % 2 == 0));
+    assert!(!m.rotate_to(|&x| x == 5));
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 4, 1]));
+
+    let mut m = list_from(&[1, 2]);
+    assert!(m.rotate_to(|&x| x == 2));
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 1]));
+
+    assert!(!LinkedList::<i32>::new().rotate_to(|_| true));
+}
+
+#[test]
+fn test_concat() {
+    let lists = vec![
+        LinkedList::new(),
+        list_from(&[1, 2]),
+        LinkedList::new(),
+        list_from(&[3]),
+        list_from(&[4, 5, 6]),
+        LinkedList::new(),
+    ];
+    let m = LinkedList::concat(lists);
+    check_links(&m);
+    assert_eq!(m.len(), 6);
+    assert_eq!(m, list_from(&[1, 2, 3, 4, 5, 6]));
+
+    let m = LinkedList::<i32>::concat(vec![LinkedList::new(), LinkedList::new()]);
+    check_links(&m);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_find_middle() {
+    assert_eq!(list_from(&[1, 2, 3, 4, 5]).find_middle(), Some(&3));
+    assert_eq!(list_from(&[1, 2, 3, 4]).find_middle(), Some(&2));
+    assert_eq!(list_from(&[1, 2]).find_middle(), Some(&1));
+    assert_eq!(list_from(&[1]).find_middle(), Some(&1));
+    assert_eq!(LinkedList::<i32>::new().find_middle(), None);
+}
+
+#[test]
+fn test_rotate_one() {
+    let mut m = list_from(&[1, 2, 3]);
+    m.rotate_right_one();
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 1, 2]));
+    m.rotate_left_one();
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+    m.rotate_left_one();
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 1]));
+
+    let mut m = list_from(&[1]);
+    m.rotate_right_one();
+    m.rotate_left_one();
+    check_links(&m);
+    assert_eq!(m, list_from(&[1]));
+
+    let mut m = LinkedList::<i32>::new();
+    m.rotate_right_one();
+    m.rotate_left_one();
+    check_links(&m);
+}
+
+#[test]
+fn test_pop_if() {
+    let mut m = list_from(&[1, 2, 3]);
+    assert_eq!(m.pop_front_if(|&x| x == 2), None);
+    assert_eq!(m.pop_back_if(|&x| x == 2), None);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+
+    assert_eq!(m.pop_front_if(|&x| x == 1), Some(1));
+    check_links(&m);
+    assert_eq!(m.pop_back_if(|&x| x == 3), Some(3));
+    check_links(&m);
+    assert_eq!(m, list_from(&[2]));
+
+    assert_eq!(m.pop_back_if(|_| true), Some(2));
+    assert_eq!(m.pop_front_if(|_| true), None);
+    check_links(&m);
+}
+
+#[test]
+fn test_into_chunks() {
+    let v: Vec<i32> = (0..7).collect();
+    let mut chunks = list_from(&v).into_chunks(3);
+    assert_eq!(chunks.size_hint(), (3, Some(3)));
+    let chunks: Vec<_> = chunks.by_ref().collect();
+    for chunk in &chunks {
+        check_links(chunk);
+    }
+    assert_eq!(
+        chunks,
+        [
+            list_from(&[0, 1, 2]),
+            list_from(&[3, 4, 5]),
+            list_from(&[6])
+        ]
+    );
+
+    // Dropping a partially consumed iterator frees the rest.
+    let strings: Vec<String> = v.iter().map(i32::to_string).collect();
+    let mut chunks = list_from(&strings).into_chunks(2);
+    let first = chunks.next().unwrap();
+    check_links(&first);
+    assert_eq!(first, list_from(&strings[..2]));
+    drop(chunks);
+
+    assert_eq!(LinkedList::<i32>::new().into_chunks(1).next(), None);
+}
+
+#[test]
+#[should_panic]
+fn test_into_chunks_zero() {
+    list_from(&[1, 2, 3]).into_chunks(0);
+}
+
+#[test]
+fn test_eq_ignore_order() {
+    assert!(list_from(&[1, 2, 3]).eq_ignore_order(&list_from(&[3, 1, 2])));
+    assert!(list_from(&[1, 1, 2]).eq_ignore_order(&list_from(&[1, 2, 1])));
+    assert!(!list_from(&[1, 1, 2]).eq_ignore_order(&list_from(&[1, 2, 2])));
+    assert!(!list_from(&[1, 2]).eq_ignore_order(&list_from(&[1, 2, 2])));
+    assert!(LinkedList::<i32>::new().eq_ignore_order(&LinkedList::new()));
+}
+
+#[test]
+fn test_drain_while() {
+    let mut m = list_from(&[1, 3, 5, 6, 7]);
+    let drained: Vec<_> = m.drain_while(|&x| x % 2 == 1).collect();
+    assert_eq!(drained, [1, 3, 5]);
+    check_links(&m);
+    assert_eq!(m, list_from(&[6, 7]));
+
+    // The iterator stops at the first mismatch even if later elements match.
+    let mut drain = m.drain_while(|&x| x != 6);
+    assert_eq!(drain.next(), None);
+    assert_eq!(drain.next(), None);
+    check_links(&m);
+    assert_eq!(m.len(), 2);
+
+    // Dropping it early keeps what was already yielded removed.
+    let mut m = list_from(&[1, 2, 3, 4]);
+    assert_eq!(m.drain_while(|&x| x < 4).next(), Some(1));
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 4]));
+
+    assert_eq!(m.drain_while(|_| true).count(), 3);
+    check_links(&m);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_get_many_mut() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    let [a, b, c] = m.get_many_mut([4, 0, 2]).unwrap();
+    mem::swap(a, b);
+    *c *= 10;
+    check_links(&m);
+    assert_eq!(m, list_from(&[5, 2, 30, 4, 1]));
+
+    assert!(m.get_many_mut([1, 3, 1]).is_none());
+    assert!(m.get_many_mut([0, 5]).is_none());
+    assert_eq!(m.get_many_mut([]), Some([]));
+}
+
+#[test]
+fn test_iter_rev() {
+    let m = list_from(&[1, 2, 3]);
+    assert!(m.iter_rev().eq(&[3, 2, 1]));
+    let mut iter = m.iter_rev();
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next_back(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&2));
+    assert_eq!(iter.next(), None);
+    assert!(m.iter_rev().rev().eq(&m));
+    assert_eq!(LinkedList::<i32>::new().iter_rev().next(), None);
+}
+
+#[test]
+fn test_append_front() {
+    let mut m = list_from(&[4, 5]);
+    let mut n = list_from(&[1, 2, 3]);
+    m.append_front(&mut n);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m, list_from(&[1, 2, 3, 4, 5]));
+    assert_eq!(n.len(), 0);
+
+    // Empty to non-empty
+    m.append_front(&mut n);
+    check_links(&m);
+    assert_eq!(m.len(), 5);
+
+    // Non-empty to empty
+    n.append_front(&mut m);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(n, list_from(&[1, 2, 3, 4, 5]));
+    assert_eq!(m.len(), 0);
+    n.push_front(0);
+    assert_eq!(n.first(), Some(&0));
+    check_links(&n);
+
+    let mut o = list_from(&[-2, -1]);
+    n.prepend(&mut o);
+    check_links(&n);
+    assert_eq!(n, list_from(&[-2, -1, 0, 1, 2, 3, 4, 5]));
+    assert!(o.is_empty());
+}
+
+#[test]
+fn test_clone_with_live_iters() {
+    let m = list_from(&[1, 2, 3, 4]);
+    let mut front = m.iter();
+    let mut back = m.iter();
+    front.next();
+    back.next_back();
+    back.next_back();
+
+    assert_eq!(format!("{:?}", front), "Iter([2, 3, 4], 3)");
+    assert_eq!(format!("{:?}", back), "Iter([1, 2], 2)");
+    let n = m.clone();
+    assert_eq!(format!("{:?}", front), "Iter([2, 3, 4], 3)");
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m, n);
+
+    assert_eq!(front.next(), Some(&2));
+    assert_eq!(back.next(), Some(&1));
+    assert!(front.eq(&[3, 4]));
+    assert!(back.eq(&[2]));
+}
+
+#[test]
+fn test_copy_into_slice() {
+    let m = list_from(&[1, 2, 3]);
+    let mut short = [0; 2];
+    assert_eq!(m.copy_into_slice(&mut short), 2);
+    assert_eq!(short, [1, 2]);
+    let mut exact = [0; 3];
+    assert_eq!(m.copy_into_slice(&mut exact), 3);
+    assert_eq!(exact, [1, 2, 3]);
+    let mut long = [0; 5];
+    assert_eq!(m.copy_into_slice(&mut long), 3);
+    assert_eq!(long, [1, 2, 3, 0, 0]);
+    assert_eq!(LinkedList::new().copy_into_slice(&mut long), 0);
+}
+
+#[test]
+fn test_split_off_back() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    let n = m.split_off_back(2);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+    assert_eq!(n, list_from(&[4, 5]));
+
+    let n = m.split_off_back(0);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m.len(), 3);
+    assert_eq!(n.len(), 0);
+
+    let n = m.split_off_back(3);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m.len(), 0);
+    assert_eq!(n, list_from(&[1, 2, 3]));
+}
+
+#[test]
+#[should_panic]
+fn test_split_off_back_out_of_bounds() {
+    list_from(&[1, 2, 3]).split_off_back(4);
+}
+
+#[test]
+fn test_zip_with() {
+    let m = list_from(&[1, 2, 3]).zip_with(list_from(&[10, 20, 30, 40]), |a, b| a + b);
+    check_links(&m);
+    assert_eq!(m, list_from(&[11, 22, 33]));
+
+    let a = list_from(&[String::from("a"), String::from("b")]);
+    let b = list_from(&[String::from("x")]);
+    let m = a.zip_with(b, |a, b| a + &b);
+    check_links(&m);
+    assert_eq!(m, list_from(&[String::from("ax")]));
+
+    let m = LinkedList::<i32>::new().zip_with(list_from(&[1]), |a, b| a + b);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_retain_with_index() {
+    let mut m = list_from(&[10, 20, 30, 40, 50]);
+    m.retain_with_index(|i, _| i % 2 == 0);
+    check_links(&m);
+    assert_eq!(m, list_from(&[10, 30, 50]));
+
+    let mut seen = Vec::new();
+    m.retain_with_index(|i, &x| {
+        seen.push((i, x));
+        i == 1
+    });
+    check_links(&m);
+    assert_eq!(seen, [(0, 10), (1, 30), (2, 50)]);
+    assert_eq!(m, list_from(&[30]));
+}
+
+#[test]
+fn test_prepend_ordered() {
+    let mut m = list_from(&[9]);
+    m.prepend_ordered(vec![1, 2, 3]);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 9]));
+
+    m.prepend_ordered(Vec::new());
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 9]));
+
+    let mut m = LinkedList::new();
+    m.prepend_ordered(1..3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2]));
+}
+
+#[test]
+fn test_truncate_front() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    m.truncate_front(2);
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 5]));
+    m.truncate_front(3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 5]));
+    m.truncate_front(1);
+    check_links(&m);
+    assert_eq!(m, list_from(&[5]));
+    m.truncate_front(0);
+    check_links(&m);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_truncate() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    m.truncate(8);
+    check_links(&m);
+    assert_eq!(m, (0..8).collect::<Vec<_>>());
+    m.truncate(3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 2]));
+    m.truncate(5);
+    assert_eq!(m.len(), 3);
+    m.truncate(0);
+    check_links(&m);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_replace() {
+    let mut m = list_from(&[1, 2, 3]);
+    assert_eq!(m.replace(1, 7), 2);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 7, 3]));
+
+    let v: Vec<i32> = (0..9).collect();
+    let mut m = list_from(&v);
+    for i in 0..9 {
+        assert_eq!(m.replace(i, -(i as i32)), i as i32);
+    }
+    check_links(&m);
+    assert!(m.iter().copied().eq(v.iter().map(|x| -x)));
+}
+
+#[test]
+#[should_panic]
+fn test_replace_out_of_bounds() {
+    list_from(&[1, 2, 3]).replace(3, 0);
+}
+
+#[test]
+fn test_run_length_encode() {
+    let m = list_from(&[1, 1, 2, 3, 3, 3]).run_length_encode();
+    check_links(&m);
+    assert_eq!(m, list_from(&[(1, 2), (2, 1), (3, 3)]));
+
+    let m = list_from(&[1, 2, 3]).run_length_encode();
+    assert_eq!(m, list_from(&[(1, 1), (2, 1), (3, 1)]));
+
+    let m = list_from(&vec![String::from("a"); 3]).run_length_encode();
+    assert_eq!(m, list_from(&[(String::from("a"), 3)]));
+
+    assert_eq!(LinkedList::<i32>::new().run_length_encode().len(), 0);
+}
+
+#[test]
+fn test_rposition() {
+    let m = list_from(&[1, 2, 3, 4, 5]);
+    assert_eq!(m.rposition(|&x| x % 2 == 0), Some(3));
+    assert_eq!(m.rposition(|&x| x == 5), Some(4));
+    assert_eq!(m.rposition(|&x| x == 1), Some(0));
+    assert_eq!(m.rposition(|&x| x > 5), None);
+    assert_eq!(LinkedList::<i32>::new().rposition(|_| true), None);
+}
+
+#[test]
+fn test_pop_nth() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    assert_eq!(m.pop_nth(0), Some(1));
+    check_links(&m);
+    assert_eq!(m.pop_nth(3), Some(5));
+    check_links(&m);
+    assert_eq!(m.pop_nth(1), Some(3));
+    check_links(&m);
+    assert_eq!(m.pop_nth(2), None);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 4]));
+    assert_eq!(m.pop_nth(1), Some(4));
+    assert_eq!(m.pop_nth(0), Some(2));
+    assert_eq!(m.pop_nth(0), None);
+    check_links(&m);
+}
+
+#[test]
+fn test_iter_indexed() {
+    let m = list_from(&['a', 'b', 'c', 'd', 'e']);
+    let mut iter = m.iter_indexed();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some((0, &'a')));
+    assert_eq!(iter.next_back(), Some((4, &'e')));
+    assert_eq!(iter.next_back(), Some((3, &'d')));
+    assert_eq!(iter.next(), Some((1, &'b')));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some((2, &'c')));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let back: Vec<_> = m.iter_indexed().rev().map(|(i, _)| i).collect();
+    assert_eq!(back, [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn test_clone_reversed() {
+    let m = list_from(&[1, 2, 3]);
+    let n = m.clone_reversed();
+    check_links(&n);
+    assert_eq!(n, list_from(&[3, 2, 1]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+    assert_eq!(LinkedList::<i32>::new().clone_reversed().len(), 0);
+}
+
+#[test]
+fn test_append_n() {
+    let mut m = list_from(&[9]);
+    let mut n = list_from(&[1, 2, 3]);
+    assert_eq!(m.append_n(&mut n, 2), 2);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m, list_from(&[9, 1, 2]));
+    assert_eq!(n, list_from(&[3]));
+
+    assert_eq!(m.append_n(&mut n, 5), 1);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m, list_from(&[9, 1, 2, 3]));
+    assert_eq!(n.len(), 0);
+
+    assert_eq!(n.append_n(&mut m, 0), 0);
+    assert_eq!(n.append_n(&mut m, 3), 3);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(n, list_from(&[9, 1, 2]));
+    assert_eq!(m, list_from(&[3]));
+}
+
+#[test]
+fn test_min_max_element() {
+    let m = list_from(&[3, 1, 2]);
+    assert_eq!(m.min_element(), Some(&1));
+    assert_eq!(m.max_element(), Some(&3));
+    assert_eq!(LinkedList::<i32>::new().min_element(), None);
+    assert_eq!(LinkedList::<i32>::new().max_element(), None);
+
+    // Ties resolve to the first element.
+    struct Keyed(i32, char);
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Keyed {}
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let m: LinkedList<_> = vec![Keyed(1, 'a'), Keyed(0, 'b'), Keyed(1, 'c'), Keyed(0, 'd')]
+        .into_iter()
+        .collect();
+    assert_eq!(m.min_element().unwrap().1, 'b');
+    assert_eq!(m.max_element().unwrap().1, 'a');
+}
+
+#[test]
+fn test_bring_to_front() {
+    let mut m = list_from(&[1, 2, 3, 4]);
+    assert!(m.bring_to_front(&3));
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 1, 2, 4]));
+    assert!(m.bring_to_front(&4));
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 3, 1, 2]));
+    assert!(m.bring_to_front(&4));
+    assert!(!m.bring_to_front(&5));
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 3, 1, 2]));
+}
+
+#[test]
+fn test_is_sorted() {
+    assert!(list_from(&[1, 2, 2, 5]).is_sorted());
+    assert!(!list_from(&[1, 3, 2, 5]).is_sorted());
+    assert!(list_from(&[1]).is_sorted());
+    assert!(LinkedList::<i32>::new().is_sorted());
+    assert!(!list_from(&[1.0, f64::NAN]).is_sorted());
+
+    assert!(list_from(&[5, 3, 1]).is_sorted_by(|a, b| a >= b));
+    assert!(!list_from(&[1, 2, 2]).is_sorted_by(|a, b| a < b));
+
+    assert!(list_from(&[-1, 2, -3, 4]).is_sorted_by_key(|x: &i32| x.abs()));
+    assert!(!list_from(&[-1, 3, -2]).is_sorted_by_key(|x: &i32| x.abs()));
+    assert!(!list_from(&[1.0, f64::NAN]).is_sorted_by_key(|&x| x));
+
+    // Stops at the first pair out of order.
+    let mut calls = 0;
+    assert!(!list_from(&[2, 1, 3, 4]).is_sorted_by_key(|&x| {
+        calls += 1;
+        x
+    }));
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn test_trim() {
+    let mut m = list_from(&[0, 0, 1, 0, 2, 0]);
+    m.trim(|&x| x == 0);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 0, 2]));
+    m.trim(|&x| x == 0);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 0, 2]));
+    m.trim(|_| true);
+    check_links(&m);
+    assert_eq!(m.len(), 0);
+}
+
+#[test]
+fn test_window_map() {
+    let m = list_from(&[1, 2, 3, 4]);
+    let sums = m.window_map(2, |w| w.iter().copied().sum::<i32>());
+    check_links(&sums);
+    assert_eq!(sums, list_from(&[3, 5, 7]));
+
+    let v: Vec<i32> = (0..20).collect();
+    let windows = list_from(&v).window_map(3, |w| w.iter().map(|&&x| x).collect::<Vec<_>>());
+    assert!(windows
+        .iter()
+        .eq(v.windows(3).map(<[i32]>::to_vec).collect::<Vec<_>>().iter()));
+
+    assert_eq!(m.window_map(4, |w| w.len()), list_from(&[4]));
+    assert_eq!(m.window_map(5, |w| w.len()).len(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_window_map_zero() {
+    list_from(&[1, 2, 3]).window_map(0, |_| ());
+}
+
+#[test]
+fn test_try_for_each() {
+    let m = list_from(&[1, 2, 3, 4]);
+    let mut seen = Vec::new();
+    let res = m.try_for_each(|&x| {
+        seen.push(x);
+        if x < 3 {
+            Ok(())
+        } else {
+            Err(x)
+        }
+    });
+    assert_eq!(res, Err(3));
+    assert_eq!(seen, [1, 2, 3]);
+
+    let mut sum = 0;
+    let res: Result<(), ()> = m.try_for_each(|&x| {
+        sum += x;
+        Ok(())
+    });
+    assert_eq!(res, Ok(()));
+    assert_eq!(sum, 10);
+    assert_eq!(LinkedList::<i32>::new().try_for_each(|_| Err(())), Ok(()));
+    check_links(&m);
+}
+
+#[test]
+fn test_is_empty_clear() {
+    let mut m = list_from(&[String::from("a"), String::from("b")]);
+    assert!(!m.is_empty());
+    m.clear();
+    check_links(&m);
+    assert!(m.is_empty());
+    assert_eq!(m.len(), 0);
+    m.push_back(String::from("c"));
+    check_links(&m);
+    assert_eq!(m, list_from(&[String::from("c")]));
+    assert!(LinkedList::<i32>::new().is_empty());
+}
+
+#[test]
+fn test_clear_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct D<'a>(&'a Cell<usize>, bool);
+    impl Drop for D<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+            if self.1 {
+                panic!("panic in drop");
+            }
+        }
+    }
+
+    let drops = Cell::new(0);
+    let mut m = LinkedList::new();
+    m.push_back(D(&drops, false));
+    m.push_back(D(&drops, true));
+    m.push_back(D(&drops, false));
+    assert!(catch_unwind(AssertUnwindSafe(|| m.clear())).is_err());
+    assert_eq!(drops.get(), 3);
+    check_links(&m);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_front_back() {
+    let mut m = LinkedList::new();
+    assert_eq!(m.front(), None);
+    assert_eq!(m.back(), None);
+    m.push_back(1);
+    assert_eq!(m.front(), Some(&1));
+    assert_eq!(m.back(), Some(&1));
+    m.push_front(0);
+    m.push_back(2);
+    assert_eq!(m.front(), Some(&0));
+    assert_eq!(m.back(), Some(&2));
+    assert_eq!(m.first(), m.front());
+    assert_eq!(m.last(), m.back());
+    m.pop_front();
+    m.pop_back();
+    assert_eq!(m.front(), Some(&1));
+    assert_eq!(m.back(), Some(&1));
+}
+
+#[test]
+fn test_front_back_mut() {
+    let mut m = LinkedList::<i32>::new();
+    assert_eq!(m.front_mut(), None);
+    assert_eq!(m.back_mut(), None);
+
+    let mut m = list_from(&[1, 2, 3]);
+    *m.front_mut().unwrap() += 10;
+    *m.back_mut().unwrap() *= 10;
+    check_links(&m);
+    assert_eq!(m, list_from(&[11, 2, 30]));
+
+    let mut m = list_from(&[5]);
+    *m.front_mut().unwrap() += 1;
+    *m.back_mut().unwrap() += 1;
+    check_links(&m);
+    assert_eq!(m, list_from(&[7]));
+}
+
+#[test]
+fn test_contains() {
+    assert!(!LinkedList::<i32>::new().contains(&1));
+    let m = list_from(&[1, 2, 3]);
+    assert!(m.contains(&1));
+    assert!(m.contains(&2));
+    assert!(m.contains(&3));
+    assert!(!m.contains(&4));
+}
+
+#[test]
+fn test_iter_mut() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    for elem in m.iter_mut() {
+        *elem *= 10;
+    }
+    check_links(&m);
+    assert_eq!(m, list_from(&[10, 20, 30, 40, 50]));
+
+    let mut iter = m.iter_mut();
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    *iter.next().unwrap() += 1;
+    *iter.next_back().unwrap() += 2;
+    assert_eq!(format!("{:?}", iter), "IterMut([20, 30, 40], 3)");
+    *iter.next_back().unwrap() += 3;
+    *iter.next().unwrap() += 4;
+    *iter.next().unwrap() += 5;
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+    check_links(&m);
+    assert_eq!(m, list_from(&[11, 24, 35, 43, 52]));
+
+    for elem in &mut m {
+        *elem %= 10;
+    }
+    assert_eq!(m, list_from(&[1, 4, 5, 3, 2]));
+    assert!(LinkedList::<i32>::new().iter_mut().next().is_none());
+}
+
+#[test]
+fn test_send_sync() {
+    fn check<T: Send + Sync>(_: T) {}
+
+    let mut m = list_from(&[1, 2, 3]);
+    check(m.iter());
+    check(m.iter_mut());
+    check(m);
+}
+
+#[test]
+fn test_into_iter() {
+    let m = list_from(&[1, 2, 3, 4, 5]);
+    let mut iter = m.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.clone().collect::<Vec<_>>(), [2, 3, 4]);
+    assert_eq!(iter.next_back(), Some(4));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let v: Vec<_> = list_from(&[1, 2, 3]).into_iter().rev().collect();
+    assert_eq!(v, [3, 2, 1]);
+
+    // Unconsumed elements are dropped with the iterator.
+    let m = list_from(&[String::from("a"), String::from("b"), String::from("c")]);
+    let mut iter = m.into_iter();
+    assert_eq!(iter.next().as_deref(), Some("a"));
+    drop(iter);
+}
+
+#[test]
+fn test_split_off() {
+    let v: Vec<i32> = (0..10).collect();
+    for at in 0..=10 {
+        let mut m = list_from(&v);
+        let n = m.split_off(at);
+        check_links(&m);
+        check_links(&n);
+        assert!(m.iter().eq(&v[..at]));
+        assert!(n.iter().eq(&v[at..]));
+    }
+
+    let mut m = LinkedList::<i32>::new();
+    let n = m.split_off(0);
+    check_links(&m);
+    check_links(&n);
+    assert!(m.is_empty() && n.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_split_off_out_of_bounds() {
+    list_from(&[1, 2, 3]).split_off(4);
+}
+
+#[test]
+fn test_remove() {
+    let mut m = list_from(&[1, 2, 3, 4, 5, 6]);
+    assert_eq!(m.remove(4), 5);
+    check_links(&m);
+    assert_eq!(m.remove(1), 2);
+    check_links(&m);
+    assert_eq!(m.remove(0), 1);
+    check_links(&m);
+    assert_eq!(m.remove(2), 6);
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 4]));
+}
+
+#[test]
+#[should_panic(expected = "Cannot remove at index 3 from a list of length 3")]
+fn test_remove_out_of_bounds() {
+    list_from(&[1, 2, 3]).remove(3);
+}
+
+#[test]
+fn test_retain() {
+    let mut m = list_from(&[1, 2, 3, 4, 5, 6]);
+    m.retain(|&x| x % 2 == 0);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 4, 6]));
+    m.retain(|&x| x != 6);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 4]));
+
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    m.retain_mut(|x| {
+        *x *= 10;
+        *x != 10 && *x != 30
+    });
+    check_links(&m);
+    assert_eq!(m, list_from(&[20, 40, 50]));
+    m.retain_mut(|_| false);
+    check_links(&m);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_extract_if() {
+    let mut m = list_from(&[1, 2, 3, 4, 5, 6]);
+    let evens: Vec<_> = m.extract_if(|x| *x % 2 == 0).collect();
+    assert_eq!(evens, [2, 4, 6]);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 3, 5]));
+
+    let mut m = list_from(&[1, 2, 3, 4, 5, 6]);
+    {
+        let mut iter = m.extract_if(|x| *x != 3);
+        assert_eq!(iter.size_hint(), (0, Some(6)));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(iter.size_hint(), (0, Some(2)));
+    }
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 5, 6]));
+
+    let mut m = list_from(&[1, 2, 3]);
+    assert_eq!(m.extract_if(|_| true).count(), 3);
+    check_links(&m);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_extract_if_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        m.extract_if(|x| {
+            if *x == 4 {
+                panic!("panic in filter");
+            }
+            *x % 2 == 1
+        })
+        .for_each(drop)
+    }));
+    assert!(res.is_err());
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 4, 5]));
+}
+
+#[test]
+fn test_cursor() {
+    let m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front();
+    assert_eq!(c.current(), Some(&1));
+    c.move_next();
+    assert_eq!(c.current(), Some(&2));
+    c.move_next();
+    assert_eq!(c.current(), Some(&3));
+    c.move_next();
+    assert_eq!(c.current(), None);
+    c.move_next();
+    assert_eq!(c.current(), Some(&1));
+    c.move_prev();
+    assert_eq!(c.current(), None);
+    c.move_prev();
+    assert_eq!(c.current(), Some(&3));
+    c.move_prev();
+    assert_eq!(c.current(), Some(&2));
+    let mut d = c.clone();
+    d.move_prev();
+    assert_eq!(d.current(), Some(&1));
+    assert_eq!(c.current(), Some(&2));
+
+    let mut c = m.cursor_back();
+    assert_eq!(c.current(), Some(&3));
+    c.move_prev();
+    c.move_prev();
+    assert_eq!(c.current(), Some(&1));
+    c.move_next();
+    assert_eq!(c.current(), Some(&2));
+
+    let empty = LinkedList::<i32>::new();
+    let mut c = empty.cursor_front();
+    assert_eq!(c.current(), None);
+    c.move_next();
+    assert_eq!(c.current(), None);
+    c.move_prev();
+    assert_eq!(c.current(), None);
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front_mut();
+    c.move_next();
+    c.insert_before(10);
+    c.insert_after(20);
+    assert_eq!(c.current(), Some(&mut 2));
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 10));
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.current(), Some(&mut 20));
+    *c.current().unwrap() = 21;
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 10, 2, 21, 3]));
+
+    let mut c = m.cursor_back_mut();
+    c.insert_after(4);
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.current(), None);
+    c.move_next();
+    c.insert_before(0);
+    assert_eq!(c.current(), Some(&mut 1));
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 0));
+    c.move_prev();
+    c.insert_before(5);
+    c.insert_after(-1);
+    check_links(&m);
+    assert_eq!(m, list_from(&[-1, 0, 1, 10, 2, 21, 3, 4, 5]));
+
+    let mut c = m.cursor_front_mut();
+    assert_eq!(c.remove_current(), Some(-1));
+    assert_eq!(c.current(), Some(&mut 0));
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.remove_current(), Some(10));
+    assert_eq!(c.current(), Some(&mut 2));
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 1));
+    let mut c = m.cursor_back_mut();
+    assert_eq!(c.remove_current(), Some(5));
+    assert_eq!(c.current(), None);
+    assert_eq!(c.prev_link(), None);
+    assert_eq!(c.remove_current(), None);
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 4));
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 2, 21, 3, 4]));
+
+    let mut m = LinkedList::new();
+    let mut c = m.cursor_front_mut();
+    c.insert_after(2);
+    c.insert_before(3);
+    c.insert_after(1);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+}
+
+#[test]
+fn test_cursor_remove_current_as_list() {
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front_mut();
+    c.move_next();
+    let removed = c.remove_current_as_list().unwrap();
+    check_links(&removed);
+    assert_eq!(removed, list_from(&[2]));
+    let node = removed.front().unwrap() as *const i32;
+    assert_eq!(c.current(), Some(&mut 3));
+    c.move_next();
+    assert!(c.remove_current_as_list().is_none());
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 3]));
+    let mut c = m.cursor_back_mut();
+    assert_eq!(c.remove_current_as_list().unwrap(), list_from(&[3]));
+    assert_eq!(c.current(), None);
+    assert_eq!(c.prev_link(), None);
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 1));
+    c.insert_after(3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 3]));
+
+    let mut other = list_from(&[4]);
+    other.append(&mut { removed });
+    assert_eq!(other.back().unwrap() as *const i32, node);
+    assert_eq!(other, list_from(&[4, 2]));
+}
+
+#[test]
+fn test_cursor_splice() {
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front_mut();
+    c.move_next();
+    c.splice_before(list_from(&[10, 11]));
+    c.splice_after(list_from(&[20, 21]));
+    c.splice_before(LinkedList::new());
+    c.splice_after(LinkedList::new());
+    assert_eq!(c.current(), Some(&mut 2));
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 11));
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.current(), Some(&mut 20));
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 10, 11, 2, 20, 21, 3]));
+
+    let mut c = m.cursor_front_mut();
+    c.splice_before(list_from(&[-1, 0]));
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 0));
+    let mut c = m.cursor_back_mut();
+    c.splice_after(list_from(&[4]));
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.current(), None);
+    c.splice_before(list_from(&[5]));
+    c.splice_after(list_from(&[-2]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[-2, -1, 0, 1, 10, 11, 2, 20, 21, 3, 4, 5]));
+
+    let mut m = LinkedList::new();
+    m.cursor_front_mut().splice_after(list_from(&[1, 2]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2]));
+}
+
+#[test]
+fn test_cursor_split() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    let mut c = m.cursor_front_mut();
+    c.move_next();
+    c.move_next();
+    let back = c.split_after();
+    assert_eq!(c.current(), Some(&mut 3));
+    assert!(c.split_after().is_empty());
+    let front = c.split_before();
+    assert_eq!(c.current(), Some(&mut 3));
+    assert!(c.split_before().is_empty());
+    c.move_prev();
+    assert_eq!(c.current(), None);
+    c.move_prev();
+    assert_eq!(c.current(), Some(&mut 3));
+    check_links(&front);
+    check_links(&back);
+    check_links(&m);
+    assert_eq!(front, list_from(&[1, 2]));
+    assert_eq!(back, list_from(&[4, 5]));
+    assert_eq!(m, list_from(&[3]));
+
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_back_mut();
+    c.move_next();
+    let all = c.split_before();
+    assert_eq!(c.current(), None);
+    c.insert_before(4);
+    c.move_next();
+    assert_eq!(c.current(), Some(&mut 4));
+    check_links(&all);
+    assert_eq!(all, list_from(&[1, 2, 3]));
+    assert_eq!(m, list_from(&[4]));
+
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front_mut();
+    c.move_prev();
+    assert_eq!(c.split_after(), list_from(&[1, 2, 3]));
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_cursor_peek() {
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front();
+    assert_eq!(
+        (c.peek_prev(), c.current(), c.peek_next()),
+        (None, Some(&1), Some(&2))
+    );
+    c.move_prev();
+    assert_eq!(
+        (c.peek_prev(), c.current(), c.peek_next()),
+        (Some(&3), None, Some(&1))
+    );
+    c.move_prev();
+    assert_eq!(
+        (c.peek_prev(), c.current(), c.peek_next()),
+        (Some(&2), Some(&3), None)
+    );
+
+    let mut c = m.cursor_front_mut();
+    c.move_next();
+    *c.peek_next().unwrap() *= 10;
+    *c.peek_prev().unwrap() *= 10;
+    let view = c.as_cursor();
+    assert_eq!(view.peek_prev(), Some(&10));
+    assert_eq!(view.current(), Some(&2));
+    assert_eq!(view.peek_next(), Some(&30));
+    let mut view = view.clone();
+    view.move_next();
+    assert_eq!(view.current(), Some(&30));
+    assert_eq!(c.current(), Some(&mut 2));
+    c.move_prev();
+    c.move_prev();
+    assert_eq!(c.peek_next(), Some(&mut 10));
+    assert_eq!(c.peek_prev(), Some(&mut 30));
+    assert_eq!(m, list_from(&[10, 2, 30]));
+
+    let empty = LinkedList::<i32>::new();
+    let c = empty.cursor_back();
+    assert_eq!(
+        (c.peek_prev(), c.current(), c.peek_next()),
+        (None, None, None)
+    );
+}
+
+#[test]
+fn test_cursor_index() {
+    let m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front();
+    assert_eq!(c.index(), Some(0));
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.index(), Some(2));
+    c.move_next();
+    assert_eq!(c.index(), None);
+    c.move_prev();
+    assert_eq!(c.index(), Some(2));
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.index(), Some(0));
+    assert_eq!(m.cursor_back().index(), Some(2));
+    assert_eq!(
+        format!("{:?}", m.cursor_back()),
+        "Cursor([1, 2, 3], Some(2))"
+    );
+    assert_eq!(LinkedList::<i32>::new().cursor_back().index(), None);
+
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_front_mut();
+    c.move_next();
+    c.insert_before(10);
+    assert_eq!(c.index(), Some(2));
+    c.insert_after(20);
+    assert_eq!(c.index(), Some(2));
+    c.splice_before(list_from(&[11, 12]));
+    assert_eq!(c.index(), Some(4));
+    c.splice_after(list_from(&[21, 22]));
+    assert_eq!(c.index(), Some(4));
+    assert_eq!(c.remove_current(), Some(2));
+    assert_eq!(c.index(), Some(4));
+    assert_eq!(c.current(), Some(&mut 21));
+    assert_eq!(c.as_cursor().index(), Some(4));
+    c.move_prev();
+    assert_eq!(c.index(), Some(3));
+    let front = c.split_before();
+    assert_eq!(front.len(), 3);
+    assert_eq!(c.index(), Some(0));
+    let back = c.split_after();
+    assert_eq!(back.len(), 4);
+    assert_eq!(c.index(), Some(0));
+    c.move_next();
+    assert_eq!(c.index(), None);
+    c.insert_after(0);
+    c.insert_before(13);
+    c.splice_after(list_from(&[-2, -1]));
+    c.splice_before(list_from(&[14]));
+    assert_eq!(c.index(), None);
+    c.move_prev();
+    assert_eq!(c.index(), Some(5));
+    check_links(&m);
+    assert_eq!(m, list_from(&[-2, -1, 0, 12, 13, 14]));
+}
+
+#[test]
+fn test_cursor_at() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    for i in 0..10 {
+        let c = m.cursor_at(i);
+        assert_eq!(c.current(), Some(&i));
+        assert_eq!(c.index(), Some(i));
+        assert_eq!(c.peek_prev(), i.checked_sub(1).as_ref());
+    }
+    let mut c = m.cursor_at(10);
+    assert_eq!(c.current(), None);
+    c.move_prev();
+    assert_eq!(c.current(), Some(&9));
+
+    let mut c = m.cursor_at_mut(7);
+    assert_eq!(c.remove_current(), Some(7));
+    c.move_prev();
+    assert_eq!(c.index(), Some(6));
+    let mut c = m.cursor_at_mut(2);
+    c.insert_before(20);
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 20, 2, 3, 4, 5, 6, 8, 9]));
+    assert_eq!(LinkedList::<i32>::new().cursor_at(0).index(), None);
+}
+
+#[test]
+#[should_panic(expected = "Cannot place a cursor at index 4 in a list of length 3")]
+fn test_cursor_at_out_of_bounds() {
+    list_from(&[1, 2, 3]).cursor_at_mut(4);
+}
+
+#[test]
+fn test_cursor_seek_to() {
+    let m: LinkedList<_> = (0..10).collect();
+    let mut c = m.cursor_front();
+    for &i in &[7, 2, 9, 0, 5, 4, 8, 1] {
+        assert!(c.seek_to(i));
+        assert_eq!(c.current(), Some(&i));
+        assert_eq!(c.index(), Some(i));
+        assert_eq!(c.peek_prev(), i.checked_sub(1).as_ref());
+    }
+    assert!(c.seek_to(10));
+    assert_eq!(c.current(), None);
+    assert_eq!(c.index(), None);
+    assert!(c.seek_to(3));
+    assert_eq!(c.current(), Some(&3));
+    assert!(!c.seek_to(11));
+    assert_eq!(c.index(), Some(3));
+
+    let mut m = list_from(&[1, 2, 3]);
+    let mut c = m.cursor_back_mut();
+    assert!(c.seek_to(0));
+    assert_eq!(c.current(), Some(&mut 1));
+    assert!(c.seek_to(2));
+    assert_eq!(c.index(), Some(2));
+    c.insert_after(4);
+    assert!(c.seek_to(3));
+    assert_eq!(c.current(), Some(&mut 4));
+    assert!(!c.seek_to(5));
+    check_links(&m);
+
+    let empty = LinkedList::<i32>::new();
+    let mut c = empty.cursor_front();
+    assert!(c.seek_to(0));
+    assert_eq!(c.current(), None);
+    assert!(!c.seek_to(1));
+}
+
+#[test]
+fn test_drain() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    let drained: Vec<_> = m.drain(2..5).collect();
+    assert_eq!(drained, [2, 3, 4]);
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 5, 6, 7, 8, 9]));
+
+    let mut d = m.drain(4..);
+    assert_eq!(d.len(), 3);
+    assert_eq!(d.next_back(), Some(9));
+    assert_eq!(d.next(), Some(7));
+    drop(d);
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 5, 6]));
+
+    {
+        let mut d = m.drain(..=1);
+        assert_eq!(d.next(), Some(0));
+    }
+    check_links(&m);
+    assert_eq!(m, list_from(&[5, 6]));
+
+    assert_eq!(m.drain(1..1).count(), 0);
+    assert_eq!(m.drain(..).collect::<Vec<_>>(), [5, 6]);
+    check_links(&m);
+    assert!(m.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "range end 4 out of bounds for a list of length 3")]
+fn test_drain_out_of_bounds() {
+    list_from(&[1, 2, 3]).drain(1..4);
+}
+
+#[test]
+fn test_splice() {
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    let removed: Vec<_> = m.splice(1..3, vec![10, 11, 12]).collect();
+    assert_eq!(removed, [2, 3]);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 10, 11, 12, 4, 5]));
+
+    assert_eq!(m.splice(..2, None).count(), 2);
+    check_links(&m);
+    assert_eq!(m, list_from(&[11, 12, 4, 5]));
+
+    assert_eq!(m.splice(4.., vec![6, 7]).count(), 0);
+    assert_eq!(m.splice(0..0, vec![0]).count(), 0);
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 11, 12, 4, 5, 6, 7]));
+
+    let mut m = LinkedList::with_capacity_limit(3);
+    m.extend(vec![1, 2, 3]);
+    assert_eq!(m.splice(.., vec![4, 5, 6]).collect::<Vec<_>>(), [1, 2, 3]);
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        m.splice(1..2, vec![7, 8]);
+    }));
+    assert!(res.is_err());
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 5, 6]));
+}
+
+#[test]
+fn test_resize_with() {
+    let mut m = list_from(&[1, 2]);
+    let mut next = 2;
+    m.resize_with(5, || {
+        next += 1;
+        next
+    });
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 4, 5]));
+    m.resize_with(1, || unreachable!());
+    check_links(&m);
+    assert_eq!(m, list_from(&[1]));
+    m.resize_with(1, || unreachable!());
+    assert_eq!(m, list_from(&[1]));
+}
+
+#[test]
+#[should_panic(expected = "capacity limit of 3 exceeded")]
+fn test_resize_with_limit() {
+    let mut m = LinkedList::<i32>::with_capacity_limit(3);
+    m.resize_with(4, Default::default);
+}
+
+#[test]
+fn test_get() {
+    let mut m: LinkedList<_> = (0..7).collect();
+    for i in 0..7 {
+        assert_eq!(m.get(i), Some(&i));
+    }
+    assert_eq!(m.get(7), None);
+    for i in 0..7 {
+        *m.get_mut(i).unwrap() *= 10;
+    }
+    assert_eq!(m.get_mut(7), None);
+    check_links(&m);
+    assert_eq!(m, (0..7).map(|i| i * 10).collect::<Vec<_>>());
+    assert_eq!(LinkedList::<i32>::new().get(0), None);
+}
+
+#[test]
+fn test_index() {
+    let mut m = list_from(&[1, 2, 3]);
+    assert_eq!(m[0], 1);
+    assert_eq!(m[2], 3);
+    m[1] = 20;
+    m[2] += 1;
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 20, 4]));
+}
+
+#[test]
+#[should_panic(expected = "index 3 out of bounds for a list of length 3")]
+fn test_index_out_of_bounds() {
+    let mut m = list_from(&[1, 2, 3]);
+    m[3] = 4;
+}
+
+#[test]
+fn test_insert() {
+    let mut m = LinkedList::new();
+    m.insert(0, 2);
+    m.insert(0, 0);
+    m.insert(1, 1);
+    m.insert(3, 4);
+    m.insert(3, 3);
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 2, 3, 4]));
+    for i in (0..=5).rev() {
+        m.insert(i, 10 + i);
+    }
+    check_links(&m);
+    assert_eq!(m, list_from(&[10, 0, 11, 1, 12, 2, 13, 3, 14, 4, 15]));
+}
+
+#[test]
+#[should_panic(expected = "Cannot insert at index 4 into a list of length 3")]
+fn test_insert_out_of_bounds() {
+    list_from(&[1, 2, 3]).insert(4, 0);
+}
+
+#[test]
+fn test_swap() {
+    let mut m: LinkedList<_> = (0..8).collect();
+    m.swap(0, 7);
+    m.swap(3, 4);
+    m.swap(5, 1);
+    m.swap(2, 2);
+    m.swap(6, 4);
+    check_links(&m);
+    assert_eq!(m, list_from(&[7, 5, 2, 4, 6, 1, 3, 0]));
+}
+
+#[test]
+#[should_panic(expected = "Cannot swap index 3 in a list of length 3")]
+fn test_swap_out_of_bounds() {
+    list_from(&[1, 2, 3]).swap(3, 0);
+}
+
+#[test]
+fn test_reverse() {
+    let mut m = list_from(&[1, 2, 3, 4]);
+    m.reverse();
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 3, 2, 1]));
+    m.push_back(0);
+    m.push_front(5);
+    assert_eq!(
+        m.iter().rev().copied().collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4, 5]
+    );
+    m.reverse();
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 2, 3, 4, 5]));
+
+    let mut m = LinkedList::<i32>::new();
+    m.reverse();
+    assert!(m.is_empty());
+
+    #[cfg(feature = "cached_hash")]
+    {
+        let mut m = list_from(&[1, 2, 3]);
+        m.cache_hash();
+        m.reverse();
+        assert!(!m.is_hash_cached());
+    }
+}
+
+#[test]
+fn test_rotate() {
+    let mut m: LinkedList<_> = (0..6).collect();
+    m.rotate_left(2);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 4, 5, 0, 1]));
+    m.rotate_right(5);
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 4, 5, 0, 1, 2]));
+    m.rotate_left(0);
+    m.rotate_right(6);
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 4, 5, 0, 1, 2]));
+    m.rotate_left(5);
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 3, 4, 5, 0, 1]));
+
+    let mut m = LinkedList::<i32>::new();
+    m.rotate_left(0);
+    m.rotate_right(0);
+    assert!(m.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Cannot rotate by 4 in a list of length 3")]
+fn test_rotate_out_of_bounds() {
+    list_from(&[1, 2, 3]).rotate_right(4);
+}
+
+#[test]
+fn test_sort() {
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::seed_from_u64(0);
+    for len in 0..40 {
+        let v: Vec<u8> = (0..len).map(|_| rng.gen_range(0, 8)).collect();
+        let mut m = list_from(&v);
+        m.sort();
+        check_links(&m);
+        let mut sorted = v.clone();
+        sorted.sort();
+        assert_eq!(m, list_from(&sorted));
+
+        // Stability: pair every element with its original index.
+        let mut m: LinkedList<_> = v.iter().copied().zip(0..).collect();
+        m.sort_by_key(|&(x, _)| x);
+        check_links(&m);
+        let mut sorted: Vec<_> = v.iter().copied().zip(0..).collect();
+        sorted.sort_by_key(|&(x, _)| x);
+        assert!(m.iter().eq(sorted.iter()));
+
+        let mut m = list_from(&v);
+        m.sort_by(|a, b| b.cmp(a));
+        let mut desc = v.clone();
+        desc.sort_by(|a, b| b.cmp(a));
+        assert_eq!(m, list_from(&desc));
+    }
+
+    // Nodes are relinked, not moved.
+    let mut m = list_from(&[3, 1, 2]);
+    let addrs: Vec<_> = m.iter().map(|x| x as *const i32).collect();
+    m.sort();
+    let sorted: Vec<_> = m.iter().map(|x| x as *const i32).collect();
+    assert_eq!(sorted, [addrs[1], addrs[2], addrs[0]]);
+}
+
+#[test]
+fn test_sort_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut m: LinkedList<_> = (0..20).rev().collect();
+    let mut calls = 0;
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        m.sort_by(|a, b| {
+            calls += 1;
+            if calls == 25 {
+                panic!("panic in compare");
+            }
+            a.cmp(b)
+        })
+    }));
+    assert!(res.is_err());
+    check_links(&m);
+    let mut v: Vec<_> = m.into_iter().collect();
+    v.sort();
+    assert_eq!(v, (0..20).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_sort_by_cached_key() {
+    let mut m: LinkedList<_> = ["10", "9", "100", "09", "1"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let mut calls = 0;
+    m.sort_by_cached_key(|s| {
+        calls += 1;
+        s.parse::<u32>().unwrap()
+    });
+    assert_eq!(calls, 5);
+    check_links(&m);
+    assert!(m.iter().eq(["1", "9", "09", "10", "100"].iter()));
+
+    let mut m = list_from(&[3, 1, 2]);
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        m.sort_by_cached_key(|&x| if x == 2 { panic!() } else { x });
+    }));
+    assert!(res.is_err());
+    check_links(&m);
+    assert_eq!(m, list_from(&[3, 1, 2]));
+
+    let mut m = LinkedList::<i32>::new();
+    m.sort_by_cached_key(|&x| x);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_merge() {
+    let mut m = list_from(&[1, 3, 5, 7]);
+    m.merge(list_from(&[0, 2, 3, 8, 9]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[0, 1, 2, 3, 3, 5, 7, 8, 9]));
+    m.merge(LinkedList::new());
+    assert_eq!(m.len(), 9);
+
+    let mut m = LinkedList::new();
+    m.merge(list_from(&[1, 2]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2]));
+
+    // Stable: on equal keys the elements of `self` come first.
+    let mut m: LinkedList<_> = vec![(1, 'a'), (2, 'a')].into_iter().collect();
+    m.merge_by(vec![(1, 'b'), (2, 'b')].into_iter().collect(), |a, b| {
+        a.0.cmp(&b.0)
+    });
+    check_links(&m);
+    assert!(m.iter().eq([(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')].iter()));
+}
+
+#[test]
+fn test_insert_sorted() {
+    let mut m = LinkedList::new();
+    for &x in &[5, 1, 4, 1, 9, 0, 5] {
+        m.insert_sorted(x);
+        check_links(&m);
+    }
+    assert_eq!(m, list_from(&[0, 1, 1, 4, 5, 5, 9]));
+
+    let mut m: LinkedList<_> = vec![(3, 'a'), (1, 'a')].into_iter().collect();
+    m.insert_sorted_by((1, 'b'), |a, b| b.0.cmp(&a.0));
+    m.insert_sorted_by((3, 'b'), |a, b| b.0.cmp(&a.0));
+    m.insert_sorted_by((2, 'b'), |a, b| b.0.cmp(&a.0));
+    check_links(&m);
+    assert!(m
+        .iter()
+        .eq([(3, 'a'), (3, 'b'), (2, 'b'), (1, 'a'), (1, 'b')].iter()));
+}
+
+#[test]
+fn test_sorted_list() {
+    let mut s: SortedList<_> = vec![5, 3, 8, 1].into_iter().collect();
+    s.insert(4);
+    s.insert(9);
+    s.insert(0);
+    check_links(s.as_list());
+    assert!(s.iter().eq([0, 1, 3, 4, 5, 8, 9].iter()));
+    assert!(s.contains(&4));
+    assert!(!s.contains(&2));
+    assert!(!s.contains(&10));
+    assert_eq!((s.min(), s.max()), (Some(&0), Some(&9)));
+
+    s.merge(vec![2, 7].into_iter().collect());
+    s.extend(vec![6, 10]);
+    check_links(s.as_list());
+    assert_eq!(s.len(), 11);
+    assert_eq!(s.pop_min(), Some(0));
+    assert_eq!(s.pop_max(), Some(10));
+    assert_eq!(s.into_list(), (1..10).collect::<Vec<_>>());
+
+    let mut s = SortedList::from(list_from(&[2, 1]));
+    assert_eq!(format!("{:?}", s), "[1, 2]");
+    s.clear();
+    assert!(s.is_empty());
+    assert_eq!(s.pop_min(), None);
+}
+
+#[test]
+fn test_dedup() {
+    let mut m = list_from(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    m.dedup();
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 1, 4]));
+
+    let mut m = list_from(&[10, 11, 20, 25, 31, 9]);
+    m.dedup_by_key(|x| *x / 10);
+    check_links(&m);
+    assert_eq!(m, list_from(&[10, 20, 31, 9]));
+
+    let mut m = list_from(&[1, 2, 4, 3, 5, 6]);
+    m.dedup_by(|a, b| {
+        if *a == *b + 1 {
+            *b = *a;
+            true
+        } else {
+            false
+        }
+    });
+    check_links(&m);
+    assert_eq!(m, list_from(&[2, 4, 3, 6]));
+
+    let mut m = list_from(&[7, 7]);
+    m.dedup();
+    check_links(&m);
+    assert_eq!(m, list_from(&[7]));
+    let mut m = LinkedList::<i32>::new();
+    m.dedup();
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_partition() {
+    let m: LinkedList<_> = (0..10).collect();
+    let (even, odd) = m.partition(|x| x % 2 == 0);
+    check_links(&even);
+    check_links(&odd);
+    assert_eq!(even, list_from(&[0, 2, 4, 6, 8]));
+    assert_eq!(odd, list_from(&[1, 3, 5, 7, 9]));
+
+    let (all, none) = list_from(&[1, 2]).partition(|_| true);
+    assert_eq!(all, list_from(&[1, 2]));
+    assert!(none.is_empty());
+}
+
+#[test]
+fn test_split() {
+    let m = list_from(&[1, 2, 0, 3, 0, 0, 4, 5]);
+    let segments: Vec<_> = m.split(|&x| x == 0).collect();
+    for segment in &segments {
+        check_links(segment);
+    }
+    assert_eq!(
+        segments,
+        [
+            list_from(&[1, 2]),
+            list_from(&[3]),
+            LinkedList::new(),
+            list_from(&[4, 5])
+        ]
+    );
+
+    let segments: Vec<_> = list_from(&[0, 1, 0]).split(|&x| x == 0).collect();
+    assert_eq!(
+        segments,
+        [LinkedList::new(), list_from(&[1]), LinkedList::new()]
+    );
+
+    let mut split = LinkedList::<i32>::new().split(|_| true);
+    assert_eq!(split.size_hint(), (1, Some(1)));
+    assert_eq!(split.next(), Some(LinkedList::new()));
+    assert_eq!(split.next(), None);
+    assert_eq!(split.next(), None);
+}
+
+#[test]
+fn test_interleave() {
+    let mut m = list_from(&[1, 3, 5, 7, 8]);
+    let mut n = list_from(&[2, 4, 6]);
+    m.interleave(&mut n);
+    check_links(&m);
+    check_links(&n);
+    assert_eq!(m, list_from(&[1, 2, 3, 4, 5, 6, 7, 8]));
+    assert!(n.is_empty());
+
+    let mut m = list_from(&[1]);
+    let mut n = list_from(&[2, 3, 4]);
+    m.interleave(&mut n);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 4]));
+
+    let mut m = LinkedList::new();
+    m.interleave(&mut list_from(&[1, 2]));
+    assert_eq!(m, list_from(&[1, 2]));
+}
+
+#[test]
+fn test_from_iter_lists() {
+    let lists = vec![
+        list_from(&[1, 2]),
+        LinkedList::new(),
+        list_from(&[3]),
+        list_from(&[4, 5]),
+    ];
+    let addrs: Vec<_> = lists.iter().flatten().map(|x| x as *const i32).collect();
+    let m: LinkedList<i32> = lists.into_iter().collect();
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 4, 5]));
+    assert_eq!(m.iter().map(|x| x as *const i32).collect::<Vec<_>>(), addrs);
+
+    let m: LinkedList<i32> = Vec::<LinkedList<i32>>::new().into_iter().collect();
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_clone_from() {
+    let mut m = list_from(&[1, 2, 3, 4]);
+    let addrs: Vec<_> = m.iter().map(|x| x as *const i32).collect();
+    m.clone_from(&list_from(&[5, 6]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[5, 6]));
+    assert!(m
+        .iter()
+        .map(|x| x as *const i32)
+        .eq(addrs[..2].iter().copied()));
+
+    m.clone_from(&list_from(&[7, 8, 9]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[7, 8, 9]));
+    assert!(m
+        .iter()
+        .take(2)
+        .map(|x| x as *const i32)
+        .eq(addrs[..2].iter().copied()));
+
+    m.clone_from(&LinkedList::new());
+    check_links(&m);
+    assert!(m.is_empty());
+
+    let mut s: LinkedList<String> = vec![String::with_capacity(16)].into_iter().collect();
+    let buf = s.front().unwrap().as_ptr();
+    s.clone_from(&vec!["abc".to_string()].into_iter().collect());
+    assert_eq!(s.front().unwrap(), "abc");
+    assert_eq!(s.front().unwrap().as_ptr(), buf);
+
+    // The source's capacity limit replaces the destination's.
+    let mut m = LinkedList::with_capacity_limit(2);
+    m.extend(vec![1, 2]);
+    m.clone_from(&list_from(&[1, 2, 3, 4, 5]));
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3, 4, 5]));
+    assert_eq!(m.capacity_limit(), None);
+
+    let mut n = LinkedList::with_capacity_limit(3);
+    n.push_back(9);
+    m.clone_from(&n);
+    assert_eq!(m, list_from(&[9]));
+    assert_eq!(m.capacity_limit(), Some(3));
+}
+
+#[test]
+fn test_from_containers() {
+    let m = LinkedList::from([1, 2, 3]);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+    let m: LinkedList<_> = vec![4, 5].into();
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 5]));
+    let m = LinkedList::from(vec![6].into_boxed_slice());
+    assert_eq!(m, list_from(&[6]));
+    assert!(LinkedList::<i32>::from([]).is_empty());
+}
+
+#[test]
+fn test_into_vec() {
+    let m = list_from(&[1, 2, 3]);
+    let v = m.to_vec();
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), 3);
+    let v = m.into_vec();
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), 3);
+    let v: Vec<_> = list_from(&[4, 5]).into();
+    assert_eq!(v, [4, 5]);
+    assert!(LinkedList::<i32>::new().into_vec().is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_std_conversions() {
+    use std::collections::{LinkedList as StdList, VecDeque};
+
+    let std_list: StdList<_> = vec![1, 2, 3].into_iter().collect();
+    let m = LinkedList::from(std_list);
+    check_links(&m);
+    assert_eq!(m, list_from(&[1, 2, 3]));
+    let std_list = StdList::from(m);
+    assert!(std_list.iter().eq([1, 2, 3].iter()));
+
+    let deque: VecDeque<_> = vec![4, 5].into_iter().collect();
+    let m: LinkedList<_> = deque.into();
+    check_links(&m);
+    assert_eq!(m, list_from(&[4, 5]));
+    let deque: VecDeque<_> = m.into();
+    assert_eq!(deque, [4, 5]);
+}
+
+#[test]
+fn test_eq_slices() {
+    let m = list_from(&[1, 2, 3]);
+    assert_eq!(m, [1, 2, 3]);
+    assert_eq!(m, vec![1, 2, 3]);
+    assert_eq!(m, &[1, 2, 3][..]);
+    assert_eq!(m, *vec![1, 2, 3].as_slice());
+    assert_ne!(m, [1, 2]);
+    assert_ne!(m, [1, 2, 4]);
+    assert_ne!(m, vec![1, 2, 3, 4]);
+    assert_eq!(LinkedList::<i32>::new(), []);
+
+    let strings: LinkedList<String> = vec!["a".to_string()].into_iter().collect();
+    assert_eq!(strings, ["a"]);
+}
+
+#[test]
+fn test_extend_ref() {
+    let mut m = list_from(&[1]);
+    m.extend(&[2, 3]);
+    m.extend([4, 5].iter());
+    m.extend(&list_from(&[6]));
+    check_links(&m);
+    assert_eq!(m, [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_exact_size_fused() {
+    fn check<I: ExactSizeIterator + std::iter::FusedIterator>(mut iter: I, len: usize) {
+        assert_eq!(iter.len(), len);
+        for remaining in (0..len).rev() {
+            assert!(iter.next().is_some());
+            assert_eq!(iter.len(), remaining);
+        }
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+    }
+
+    let mut m = list_from(&[1, 2, 3, 4, 5]);
+    check(m.iter(), 5);
+    check(m.iter().rev().enumerate(), 5);
+    check(m.iter_mut(), 5);
+    check(m.iter_indexed(), 5);
+    check(m.pairs(), 4);
+    check(m.clone().into_iter(), 5);
+    check(m.clone().into_chunks(2), 3);
+    check(m.drain(1..3), 2);
+}
+
+#[test]
+fn test_iter_nth() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    let mut iter = m.iter();
+    assert_eq!(iter.nth(2), Some(&2));
+    assert_eq!(iter.nth_back(3), Some(&6));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.nth(1), Some(&4));
+    assert_eq!(iter.clone().collect::<Vec<_>>(), [&5]);
+    assert_eq!(iter.nth_back(1), None);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let mut iter = m.iter();
+    assert_eq!(iter.nth(10), None);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert!(m.iter().step_by(3).eq([0, 3, 6, 9].iter()));
+    assert!(m.iter().rev().skip(8).eq([1, 0].iter()));
+
+    let mut iter = m.iter_mut();
+    *iter.nth(1).unwrap() = 10;
+    *iter.nth_back(1).unwrap() = 80;
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.nth_back(6), None);
+    assert_eq!(iter.next(), None);
+    check_links(&m);
+    assert_eq!(m, [0, 10, 2, 3, 4, 5, 6, 7, 80, 9]);
+}
+
+#[test]
+fn test_iter_fold() {
+    let m: LinkedList<_> = (1..=10).collect();
+    assert_eq!(m.iter().sum::<i32>(), 55);
+    assert_eq!(m.iter().count(), 10);
+    let mut iter = m.iter();
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.clone().count(), 8);
+    assert_eq!(
+        iter.clone().fold(Vec::new(), |mut v, &x| {
+            v.push(x);
+            v
+        }),
+        [2, 3, 4, 5, 6, 7, 8, 9]
+    );
+    assert_eq!(
+        iter.rfold(Vec::new(), |mut v, &x| {
+            v.push(x);
+            v
+        }),
+        [9, 8, 7, 6, 5, 4, 3, 2]
+    );
+    let mut rev = Vec::new();
+    m.iter().rev().for_each(|&x| rev.push(x));
+    assert_eq!(rev, (1..=10).rev().collect::<Vec<_>>());
+    assert_eq!(LinkedList::<i32>::new().iter().fold(7, |_, &x| x), 7);
+}
+
+#[test]
+fn test_range() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    assert!(m.range(2..5).eq([2, 3, 4].iter()));
+    assert!(m.range(7..).rev().eq([9, 8, 7].iter()));
+    assert!(m.range(..=1).eq([0, 1].iter()));
+    assert_eq!(m.range(..).len(), 10);
+    assert_eq!(m.range(4..4).next(), None);
+    assert_eq!(m.range(10..).next(), None);
+    let mut r = m.range(1..9);
+    assert_eq!(r.next(), Some(&1));
+    assert_eq!(r.next_back(), Some(&8));
+    assert_eq!(format!("{:?}", r), "Iter([2, 3, 4, 5, 6, 7], 6)");
+
+    for x in m.range_mut(3..6) {
+        *x *= 10;
+    }
+    m.range_mut(8..).rev().for_each(|x| *x += 1);
+    check_links(&m);
+    assert_eq!(m, [0, 1, 2, 30, 40, 50, 6, 7, 9, 10]);
+}
+
+#[test]
+#[should_panic(expected = "range starts at 3 but ends at 2")]
+fn test_range_decreasing() {
+    #[allow(clippy::reversed_empty_ranges)]
+    list_from(&[1, 2, 3]).range(3..2);
+}
+
+#[test]
+fn test_xor_list_macro() {
+    let m = xor_list![1, 2, 3];
+    check_links(&m);
+    assert_eq!(m, [1, 2, 3]);
+    let m = xor_list!["a".to_string(), "b".to_string(),];
+    assert_eq!(m, ["a", "b"]);
+    let m = xor_list![vec![0]; 3];
+    check_links(&m);
+    assert_eq!(m, [vec![0], vec![0], vec![0]]);
+    let m: LinkedList<i32> = xor_list![];
+    assert!(m.is_empty());
+    assert!(xor_list![1; 0].is_empty());
+}
+
+#[test]
+fn test_from_elem_repeat_with() {
+    let m = LinkedList::from_elem(String::from("ab"), 3);
+    check_links(&m);
+    assert_eq!(m, ["ab", "ab", "ab"]);
+    assert!(LinkedList::from_elem(1, 0).is_empty());
+
+    let mut i = 0;
+    let m = LinkedList::repeat_with(4, || {
+        i += 1;
+        i * i
+    });
+    check_links(&m);
+    assert_eq!(m, [1, 4, 9, 16]);
+    assert!(LinkedList::repeat_with(0, || -> i32 { unreachable!() }).is_empty());
+}
+
+#[test]
+fn test_remove_item() {
+    let mut m = xor_list![1, 2, 3, 2, 4];
+    assert_eq!(m.remove_item(&2), Some(2));
+    check_links(&m);
+    assert_eq!(m, [1, 3, 2, 4]);
+    assert_eq!(m.remove_item(&4), Some(4));
+    assert_eq!(m.remove_item(&1), Some(1));
+    assert_eq!(m.remove_item(&5), None);
+    check_links(&m);
+    assert_eq!(m, [3, 2]);
+}
+
+#[test]
+fn test_remove_all() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    assert_eq!(m.remove_all(|x| x % 3 == 0), 4);
+    check_links(&m);
+    assert_eq!(m, [1, 2, 4, 5, 7, 8]);
+    assert_eq!(m.remove_all(|_| false), 0);
+    assert_eq!(m.remove_all(|_| true), 6);
+    check_links(&m);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_pop_n() {
+    let mut m: LinkedList<_> = (0..10).collect();
+    let front = m.pop_front_n(3);
+    check_links(&front);
+    check_links(&m);
+    assert_eq!(front, [0, 1, 2]);
+    let back = m.pop_back_n(2);
+    check_links(&back);
+    check_links(&m);
+    assert_eq!(back, [8, 9]);
+    assert_eq!(m, [3, 4, 5, 6, 7]);
+    assert!(m.pop_front_n(0).is_empty());
+    assert_eq!(m.pop_back_n(100), [3, 4, 5, 6, 7]);
+    assert!(m.is_empty());
+    assert!(m.pop_front_n(1).is_empty());
+}